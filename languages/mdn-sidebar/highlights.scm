@@ -0,0 +1,41 @@
+(comment) @comment
+
+[
+  (string_scalar)
+  (double_quote_scalar)
+  (single_quote_scalar)
+  (block_scalar)
+] @string
+
+[
+  (integer_scalar)
+  (float_scalar)
+] @number
+
+(boolean_scalar) @boolean
+
+(block_mapping_pair
+  key: (flow_node
+    (plain_scalar
+      (string_scalar) @property)))
+
+["-" ":"] @punctuation.delimiter
+
+; Keys rari gives meaning to.
+((block_mapping_pair
+  key: (flow_node
+    (plain_scalar
+      (string_scalar) @keyword)))
+  (#any-of? @keyword
+    "sidebar" "l10n" "type" "link" "title" "hash" "details" "children" "path"
+    "tags" "code" "depth" "includeParent"))
+
+; Links to pages.
+(block_mapping_pair
+  key: (flow_node
+    (plain_scalar
+      (string_scalar) @_key))
+  value: (flow_node
+    (plain_scalar
+      (string_scalar) @link_uri))
+  (#eq? @_key "link"))
@@ -0,0 +1,188 @@
+//! Heading anchors and the links that depend on them.
+
+use zed_extension_api::{Result, Worktree};
+
+use crate::{front_matter::FrontMatter, git, macros, markdown};
+
+/// Characters rari drops from heading text when building an `id`.
+const REJECTED: &[char] = &[
+    '"', '#', '$', '%', '&', '+', ',', '/', ':', ';', '=', '?', '@', '[', ']', '^', '`', '{', '|',
+    '}', '~', '\'', ')', '(', '\\', '<', '>',
+];
+
+/// The `id` rari gives a heading: the text without link targets, lowercase,
+/// whitespace as `_` and [`REJECTED`] characters dropped.
+pub fn anchor_id(heading: &str) -> String {
+    let mut text = heading.trim().to_string();
+    // `[text](url)` renders as `text`.
+    while let Some(close) = text.find("](") {
+        let Some(end) = text[close..].find(')').map(|i| close + i) else {
+            break;
+        };
+        text.replace_range(close..=end, "");
+    }
+    text.to_lowercase()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join("_")
+        .chars()
+        .filter(|c| !REJECTED.contains(c))
+        .collect()
+}
+
+/// The `id`s of all headings of a page in order. Repeated ids get `_2`,
+/// `_3`, … like rari numbers them.
+pub fn page_anchors(source: &str) -> Vec<(markdown::Heading, String)> {
+    let mut seen: Vec<String> = vec![];
+    markdown::headings(source)
+        .map(|heading| {
+            let base = anchor_id(&heading.text);
+            let count = seen.iter().filter(|id| **id == base).count();
+            seen.push(base.clone());
+            let id = if count == 0 {
+                base
+            } else {
+                format!("{base}_{}", count + 1)
+            };
+            (heading, id)
+        })
+        .collect()
+}
+
+/// Headings of live sample parts, which repeat in every example by design
+/// and aren't linked to.
+const LIVE_SAMPLE_PARTS: &[&str] = &["html", "css", "javascript", "js", "svg", "result"];
+
+/// A heading whose id repeats the id of an earlier heading, so it gets a
+/// numbered id that changes whenever headings are added before it.
+pub struct DuplicateHeading {
+    pub heading: markdown::Heading,
+    /// The numbered id rari gives it, like `examples_2`.
+    pub id: String,
+    /// Unique text for the heading, with its parent heading appended.
+    pub renamed: String,
+}
+
+impl DuplicateHeading {
+    /// The heading's line of `source` with the text renamed.
+    pub fn rewrite(&self, source: &str) -> Option<String> {
+        let line = source.lines().nth(self.heading.line)?;
+        let start = line.rfind(&self.heading.text)?;
+        let mut rewritten = line.to_string();
+        rewritten.replace_range(start..start + self.heading.text.len(), &self.renamed);
+        Some(rewritten)
+    }
+}
+
+pub fn duplicate_headings(source: &str) -> Vec<DuplicateHeading> {
+    let anchors = page_anchors(source);
+    let mut ids: Vec<String> = anchors.iter().map(|(_, id)| id.clone()).collect();
+    let mut duplicates = vec![];
+    for (i, (heading, id)) in anchors.iter().enumerate() {
+        let base = anchor_id(&heading.text);
+        if *id == base || LIVE_SAMPLE_PARTS.contains(&base.as_str()) {
+            continue;
+        }
+        let parent = anchors[..i]
+            .iter()
+            .rev()
+            .find(|(other, _)| other.level < heading.level)
+            .map(|(parent, _)| parent.text.as_str());
+        // The parent disambiguates best, a number where it doesn't.
+        let renamed = parent
+            .map(|parent| format!("{} ({parent})", heading.text))
+            .into_iter()
+            .chain((2..).map(|n| format!("{} {n}", heading.text)))
+            .find(|text| !ids.contains(&anchor_id(text)))
+            .expect("numbered names are unlimited");
+        ids.push(anchor_id(&renamed));
+        duplicates.push(DuplicateHeading {
+            heading: markdown::Heading {
+                line: heading.line,
+                level: heading.level,
+                text: heading.text.clone(),
+            },
+            id: id.clone(),
+            renamed,
+        });
+    }
+    duplicates
+}
+
+fn anchors(source: &str) -> Vec<String> {
+    page_anchors(source).into_iter().map(|(_, id)| id).collect()
+}
+
+/// The ids of the `{{EmbedLiveSample}}` calls of a page, which name the
+/// heading the sample is built from.
+pub fn live_sample_ids(source: &str) -> Vec<String> {
+    macros::calls(source)
+        .into_iter()
+        .filter(|call| call.name.eq_ignore_ascii_case("EmbedLiveSample"))
+        .filter_map(|call| call.args?.into_iter().next())
+        .map(|id| anchor_id(id.trim_matches(|c| c == '"' || c == '\'')))
+        .collect()
+}
+
+/// Every fragment a same-page link can point to.
+pub fn fragments(source: &str) -> Vec<String> {
+    let mut fragments = anchors(source);
+    for id in live_sample_ids(source) {
+        if !fragments.contains(&id) {
+            fragments.push(id);
+        }
+    }
+    fragments
+}
+
+/// A link elsewhere in the tree to an anchor that no longer exists.
+pub struct BrokenAnchor {
+    /// Worktree relative path and one based line of the link.
+    pub location: String,
+    pub link: String,
+}
+
+/// Links to anchors of `path` that existed at `base` but are gone from the
+/// working tree.
+pub fn broken_by_edit(worktree: &Worktree, path: &str, base: &str) -> Result<Vec<BrokenAnchor>> {
+    let root = worktree.root_path();
+    let old = git::run(&root, &["show", &format!("{base}:{path}")])?;
+    let new = worktree.read_text_file(path)?;
+    let slug = FrontMatter::parse(&new)
+        .and_then(|fm| fm.value("slug"))
+        .ok_or(format!("{path} has no slug"))?;
+    let current = anchors(&new);
+    let mut broken = vec![];
+    for anchor in anchors(&old)
+        .into_iter()
+        .filter(|anchor| !current.contains(anchor))
+    {
+        let link = format!("/docs/{slug}#{anchor}");
+        // Exit code 1 just means no matches.
+        let Ok(matches) = git::run(
+            &root,
+            &["grep", "-n", "-i", "-F", &link, "--", "files/*.md"],
+        ) else {
+            continue;
+        };
+        broken.extend(matches.lines().filter_map(|line| {
+            let mut parts = line.splitn(3, ':');
+            let (file, number) = (parts.next()?, parts.next()?);
+            Some(BrokenAnchor {
+                location: format!("{file}:{number}"),
+                link: link.clone(),
+            })
+        }));
+        // Links within the page itself.
+        let local = format!("](#{anchor})");
+        for (number, text) in new.lines().enumerate() {
+            if text.to_lowercase().contains(&local) {
+                broken.push(BrokenAnchor {
+                    location: format!("{path}:{}", number + 1),
+                    link: format!("#{anchor}"),
+                });
+            }
+        }
+    }
+    Ok(broken)
+}
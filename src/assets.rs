@@ -0,0 +1,151 @@
+//! Picks the rari release asset for the current platform.
+
+use serde::Deserialize;
+use zed_extension_api::{Architecture, DownloadedFileType, GithubRelease, GithubReleaseAsset, Os};
+
+/// A user provided asset for a platform, configured under
+/// `assets.<os>-<arch>`, for platforms rari has no official build for or to
+/// pick a different official one.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct AssetOverride {
+    /// Name of an asset in the latest rari release.
+    pub name: Option<String>,
+    /// Direct download URL, e.g. of a community build. Requires `sha256`.
+    pub url: Option<String>,
+    /// SHA-256 of the extracted rari executable.
+    pub sha256: Option<String>,
+}
+
+/// What to download and the work directory folder to unpack it to.
+pub struct Download {
+    pub url: String,
+    pub version_dir: String,
+    /// Version of the release, `None` for custom URLs.
+    pub version: Option<String>,
+}
+
+/// The download for the platform: the `url` of its override, which needs a
+/// `sha256` to name its folder by, or else the asset of the release
+/// `latest` returns, by the override's `name` or by platform.
+pub fn download(
+    custom: AssetOverride,
+    latest: impl FnOnce() -> Result<GithubRelease, String>,
+    arch: Architecture,
+    platform: Os,
+) -> Result<Download, String> {
+    if let Some(url) = custom.url {
+        let sha256 = custom.sha256.as_deref().ok_or(format!(
+            "a sha256 is required for the custom rari download {url}"
+        ))?;
+        let version_dir = format!("rari-custom-{}", sha256.get(..12).unwrap_or(sha256));
+        return Ok(Download {
+            url,
+            version_dir,
+            version: None,
+        });
+    }
+    let release = latest()?;
+    let asset = match custom.name {
+        Some(name) => release
+            .assets
+            .into_iter()
+            .find(|asset| asset.name == name)
+            .ok_or(format!("unable to find {name} in latest release"))?,
+        None => find(release.assets, arch, platform)?,
+    };
+    Ok(Download {
+        url: asset.download_url,
+        version_dir: format!("rari-{}", release.version),
+        version: Some(release.version),
+    })
+}
+
+/// Settings key of a platform, like `linux-x86_64` or `windows-aarch64`.
+pub fn platform_key(arch: Architecture, platform: Os) -> String {
+    let os = match platform {
+        Os::Mac => "mac",
+        Os::Linux => "linux",
+        Os::Windows => "windows",
+    };
+    let arch = match arch {
+        Architecture::Aarch64 => "aarch64",
+        Architecture::X86 => "x86",
+        Architecture::X8664 => "x86_64",
+    };
+    format!("{os}-{arch}")
+}
+
+/// How to unpack a download, judging by its name.
+pub fn file_type(name: &str) -> DownloadedFileType {
+    let name = name.to_lowercase();
+    if name.ends_with(".zip") {
+        DownloadedFileType::Zip
+    } else if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+        DownloadedFileType::GzipTar
+    } else if name.ends_with(".gz") {
+        DownloadedFileType::Gzip
+    } else {
+        DownloadedFileType::Uncompressed
+    }
+}
+
+/// The asset name rari currently publishes for a platform.
+pub fn expected_name(arch: Architecture, platform: Os) -> Result<&'static str, String> {
+    Ok(match (arch, platform) {
+        (Architecture::Aarch64, Os::Mac) => "rari-aarch64-apple-darwin.tar.gz",
+        (Architecture::Aarch64, Os::Linux) => "rari-aarch64-unknown-linux-musl.tar.gz",
+        (Architecture::Aarch64, Os::Windows) => "rari-aarch64-pc-windows-msvc.zip",
+        (Architecture::X86, _) => return Err("x86 not supported".to_string()),
+        (Architecture::X8664, Os::Mac) => "rari-x86_64-apple-darwin.tar.gz",
+        (Architecture::X8664, Os::Linux) => "rari-x86_64-unknown-linux-musl.tar.gz",
+        (Architecture::X8664, Os::Windows) => "rari-x86_64-pc-windows-msvc.zip",
+    })
+}
+
+/// Finds the asset for the platform, by exact name or, should rari rename
+/// its assets, by architecture, OS and archive tokens.
+pub fn find(
+    assets: Vec<GithubReleaseAsset>,
+    arch: Architecture,
+    platform: Os,
+) -> Result<GithubReleaseAsset, String> {
+    let expected = expected_name(arch, platform)?;
+    if let Some(asset) = assets.iter().find(|asset| asset.name == expected) {
+        return Ok(asset.clone());
+    }
+
+    let arch_tokens: &[&str] = match arch {
+        Architecture::Aarch64 => &["aarch64", "arm64"],
+        Architecture::X8664 => &["x86_64", "x86-64", "amd64", "x64"],
+        Architecture::X86 => &[],
+    };
+    let os_tokens: &[&str] = match platform {
+        Os::Mac => &["apple", "darwin", "macos"],
+        Os::Linux => &["linux"],
+        Os::Windows => &["windows", "win64"],
+    };
+    let extensions: &[&str] = match platform {
+        Os::Mac | Os::Linux => &[".tar.gz", ".tgz"],
+        Os::Windows => &[".zip"],
+    };
+    let mut candidates: Vec<&GithubReleaseAsset> = assets
+        .iter()
+        .filter(|asset| {
+            let name = asset.name.to_lowercase();
+            arch_tokens.iter().any(|token| name.contains(token))
+                && os_tokens.iter().any(|token| name.contains(token))
+                && extensions.iter().any(|extension| name.ends_with(extension))
+        })
+        .collect();
+    // Statically linked builds run everywhere.
+    candidates.sort_by_key(|asset| !asset.name.contains("musl"));
+    let asset = candidates
+        .first()
+        .ok_or(format!("unable to find {expected} in latest release"))?;
+    println!(
+        "mdn: {expected} not found, using release asset {}",
+        asset.name
+    );
+    Ok((*asset).clone())
+}
@@ -0,0 +1,110 @@
+//! Baseline status changes since the last look, from the checkout's
+//! web-features package.
+//!
+//! A snapshot of the status per BCD key is kept in the work directory, so
+//! features that became (or stopped being) Baseline after a dependency
+//! update stand out until the changes are accepted.
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    hash::{DefaultHasher, Hash, Hasher},
+};
+
+use zed_extension_api::{
+    serde_json::{self, Value},
+    Result, Worktree,
+};
+
+use crate::git;
+
+const WEB_FEATURES_DATA: &str = "node_modules/web-features/data.json";
+
+/// Baseline status (`high`, `low` or `false`) per BCD key.
+type Statuses = BTreeMap<String, String>;
+
+pub struct Change {
+    pub key: String,
+    pub before: String,
+    pub after: String,
+}
+
+fn snapshot_file(worktree: &Worktree) -> String {
+    let mut hasher = DefaultHasher::new();
+    worktree.root_path().hash(&mut hasher);
+    format!("baseline-{:x}.json", hasher.finish())
+}
+
+fn current(worktree: &Worktree) -> Result<Statuses> {
+    let json = worktree
+        .read_text_file(WEB_FEATURES_DATA)
+        .map_err(|_| format!("no {WEB_FEATURES_DATA}, run `yarn` in the checkout"))?;
+    let data: Value =
+        serde_json::from_str(&json).map_err(|e| format!("invalid {WEB_FEATURES_DATA}: {e}"))?;
+    // Older releases have the features at the top level.
+    let features = data.get("features").unwrap_or(&data);
+    let mut statuses = Statuses::new();
+    for feature in features.as_object().into_iter().flat_map(|f| f.values()) {
+        let status = match &feature["status"]["baseline"] {
+            Value::String(status) => status.clone(),
+            _ => "false".to_string(),
+        };
+        for key in feature["compat_features"].as_array().into_iter().flatten() {
+            if let Some(key) = key.as_str() {
+                statuses.insert(key.to_string(), status.clone());
+            }
+        }
+    }
+    Ok(statuses)
+}
+
+/// Keys whose status differs from the snapshot. The first call only takes
+/// the snapshot.
+pub fn changes(worktree: &Worktree) -> Result<Vec<Change>> {
+    let current = current(worktree)?;
+    let Some(snapshot) = fs::read_to_string(snapshot_file(worktree))
+        .ok()
+        .and_then(|json| serde_json::from_str::<Statuses>(&json).ok())
+    else {
+        accept(worktree)?;
+        return Ok(vec![]);
+    };
+    Ok(current
+        .into_iter()
+        .filter_map(|(key, after)| {
+            let before = snapshot
+                .get(&key)
+                .cloned()
+                .unwrap_or_else(|| "false".to_string());
+            (before != after).then_some(Change { key, before, after })
+        })
+        .collect())
+}
+
+/// Makes the current statuses the new snapshot.
+pub fn accept(worktree: &Worktree) -> Result<()> {
+    let json = serde_json::to_string(&current(worktree)?).map_err(|e| e.to_string())?;
+    fs::write(snapshot_file(worktree), json).map_err(|e| format!("failed to save snapshot: {e}"))
+}
+
+/// `file:line` of pages whose `browser-compat` lists `key`.
+pub fn pages_for(worktree: &Worktree, key: &str) -> Vec<String> {
+    let pattern = format!(
+        "^(browser-compat:|  - ) *['\"]?{}['\"]?$",
+        key.replace('.', "\\.")
+    );
+    git::run(
+        &worktree.root_path(),
+        &["grep", "-n", "-E", &pattern, "--", "files/*.md"],
+    )
+    .map(|matches| {
+        matches
+            .lines()
+            .filter_map(|line| {
+                let mut parts = line.splitn(3, ':');
+                Some(format!("{}:{}", parts.next()?, parts.next()?))
+            })
+            .collect()
+    })
+    .unwrap_or_default()
+}
@@ -0,0 +1,153 @@
+//! Runs the checks of mdn/content's CI against the files changed on a branch.
+
+use zed_extension_api::{process::Command, Result, Worktree};
+
+use crate::{
+    content, example_repos, git, glob, lint, macros::Registry, processes, rari,
+    settings::MdnSettings,
+};
+
+/// Result of one check.
+pub struct Check {
+    pub name: &'static str,
+    pub passed: bool,
+    pub output: String,
+}
+
+/// Runs `program args` in the worktree root, with the same environment as
+/// rari, returning whether it succeeded and what it printed.
+fn run_in_root(worktree: &Worktree, program: &str, args: &[String]) -> Result<(bool, String)> {
+    let output = Command::new("sh")
+        .args(["-c", processes::IN_DIR_SCRIPT])
+        .arg(worktree.root_path())
+        .arg(program)
+        .args(args.iter().cloned())
+        .envs(rari::environment(worktree))
+        .output()?;
+    let text = format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    Ok((output.status == Some(0), text))
+}
+
+fn check(name: &'static str, result: Result<(bool, String)>) -> Check {
+    let (passed, output) = result.unwrap_or_else(|e| (false, e));
+    Check {
+        name,
+        passed,
+        output: output.trim().to_string(),
+    }
+}
+
+/// The changed files and the checks run on them, in the order CI runs them.
+pub fn validate(
+    worktree: &Worktree,
+    rari_binary: &str,
+    base: &str,
+) -> Result<(Vec<String>, Vec<Check>)> {
+    let root = worktree.root_path();
+    let changed: Vec<String> = git::changed_files(&root, base)?
+        .into_iter()
+        .filter(|path| path.starts_with("files/"))
+        .collect();
+    let settings = MdnSettings::for_worktree(worktree);
+    let markdown: Vec<String> = changed
+        .iter()
+        .filter(|path| content::is_document(&settings, path))
+        .cloned()
+        .collect();
+    if changed.is_empty() {
+        return Ok((changed, vec![]));
+    }
+
+    let npx = |tool: &str, args: &[&str], files: &[String]| {
+        let args: Vec<String> = ["--no-install", tool]
+            .iter()
+            .chain(args)
+            .map(|arg| arg.to_string())
+            .chain(files.iter().cloned())
+            .collect();
+        run_in_root(worktree, "npx", &args)
+    };
+    let mut checks = vec![check("prettier", npx("prettier", &["--check"], &changed))];
+    if !markdown.is_empty() {
+        checks.push(check(
+            "markdownlint",
+            npx("markdownlint-cli2", &[], &markdown),
+        ));
+        let front_matter_args: Vec<String> = ["scripts/front-matter_linter.js".to_string()]
+            .into_iter()
+            .chain(markdown.iter().cloned())
+            .collect();
+        checks.push(check(
+            "front matter",
+            run_in_root(worktree, "node", &front_matter_args),
+        ));
+        checks.push(client_lint(worktree, &markdown));
+    }
+    if changed.iter().any(|path| path.ends_with("_redirects.txt")) {
+        checks.push(check(
+            "redirects",
+            rari::output(
+                rari_binary,
+                worktree,
+                &["content", "validate-redirects"],
+                &[],
+            ),
+        ));
+    }
+    if !markdown.is_empty() {
+        let paths: Vec<String> = markdown
+            .iter()
+            .map(|path| format!("{root}/{path}"))
+            .collect();
+        let mut args = vec!["build"];
+        for path in &paths {
+            args.extend(["--files", path]);
+        }
+        checks.push(check(
+            "rari build",
+            rari::output(rari_binary, worktree, &args, &[]),
+        ));
+    }
+    Ok((changed, checks))
+}
+
+/// The extension's own lint rules and frozen paths, failing on errors only.
+fn client_lint(worktree: &Worktree, files: &[String]) -> Check {
+    let settings = MdnSettings::for_worktree(worktree);
+    let registry = Registry::for_worktree(worktree, &settings);
+    let example_repos = example_repos::checkouts(worktree, &settings);
+    let mut output = String::new();
+    let mut passed = true;
+    for path in files {
+        if glob::matches_any(&settings.frozen, path) {
+            passed = false;
+            output.push_str(&format!(
+                "{path}:1:1: error [frozen] this page is frozen and must not be edited\n"
+            ));
+        }
+        let Ok(source) = worktree.read_text_file(path) else {
+            continue;
+        };
+        let document = lint::Document {
+            source: &source,
+            registry: &registry,
+            index: None,
+            locale: content::locale_of(path),
+            pages: None,
+            example_repos: &example_repos,
+        };
+        for diagnostic in lint::lint(&document, &settings.lint_config(path)) {
+            passed &= diagnostic.severity != lint::Severity::Error;
+            output.push_str(&format!("{path}:{diagnostic}\n"));
+        }
+    }
+    Check {
+        name: "lint",
+        passed,
+        output: output.trim().to_string(),
+    }
+}
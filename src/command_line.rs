@@ -0,0 +1,78 @@
+//! The language server command as a line to paste into a terminal, to
+//! reproduce a failing start outside of Zed, see `log_command`.
+
+use zed_extension_api::{self as zed, Command};
+
+use crate::interactive_examples;
+
+/// Variables the extension sets itself. Of the login shell environment, only
+/// rari's own `RARI_*` variables are shown.
+const EXTENSION_VARIABLES: &[&str] = &["CONTENT_ROOT", interactive_examples::ENV];
+
+/// Variables whose values are replaced, as logs end up in issues.
+const SECRET_MARKERS: &[&str] = &["TOKEN", "SECRET", "PASSWORD", "PASSWD", "API_KEY", "AUTH"];
+
+const REDACTED: &str = "<redacted>";
+
+/// `command` run from `dir` with the variables set by the extension and
+/// rari's own, for `sh` on macOS and Linux and for `cmd` on Windows. The
+/// rest of the environment is the login shell's, which a terminal has as
+/// well, so it is only summarized. Values of secrets are redacted.
+pub fn render(command: &Command, dir: &str, platform: zed::Os) -> String {
+    let (env, shell): (Vec<_>, Vec<_>) = command.env.iter().partition(|(key, _)| {
+        EXTENSION_VARIABLES.contains(&key.as_str()) || key.starts_with("RARI_")
+    });
+    let env = env.into_iter().map(|(key, value)| {
+        let upper = key.to_uppercase();
+        let secret = SECRET_MARKERS.iter().any(|marker| upper.contains(marker));
+        (key, if secret { REDACTED } else { value.as_str() })
+    });
+    let summary = match shell.len() {
+        0 => String::new(),
+        count => format!("plus the login shell environment ({count} variables)"),
+    };
+    match platform {
+        zed::Os::Mac | zed::Os::Linux => {
+            let mut line = String::new();
+            if !summary.is_empty() {
+                line.push_str(&format!("# {summary}\n"));
+            }
+            line.push_str(&format!("cd {} && \\\n", sh_quote(dir)));
+            for (key, value) in env {
+                line.push_str(&format!("  {key}={} \\\n", sh_quote(value)));
+            }
+            line.push_str(&format!("  {}", sh_quote(&command.command)));
+            for arg in &command.args {
+                line.push_str(&format!(" {}", sh_quote(arg)));
+            }
+            line
+        }
+        zed::Os::Windows => {
+            let mut lines = String::new();
+            if !summary.is_empty() {
+                lines.push_str(&format!("rem {summary}\n"));
+            }
+            lines.push_str(&format!("cd /d \"{dir}\"\n"));
+            for (key, value) in env {
+                lines.push_str(&format!("set \"{key}={value}\"\n"));
+            }
+            lines.push_str(&format!("\"{}\"", command.command));
+            for arg in &command.args {
+                lines.push_str(&format!(" \"{arg}\""));
+            }
+            lines
+        }
+    }
+}
+
+fn sh_quote(value: &str) -> String {
+    if !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "/._-:=@%+,".contains(c))
+    {
+        value.to_string()
+    } else {
+        format!("'{}'", value.replace('\'', r"'\''"))
+    }
+}
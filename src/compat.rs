@@ -0,0 +1,94 @@
+//! Browser compatibility of a symbol from prose, like `fetch()` or
+//! `scroll-snap-type`, looked up in @mdn/browser-compat-data.
+
+use zed_extension_api::{
+    serde_json::{self, Value},
+    Result, Worktree,
+};
+
+use crate::settings::MdnSettings;
+
+/// Where mdn/content installs BCD.
+const DEFAULT_DATA: &str = "node_modules/@mdn/browser-compat-data/data.json";
+
+const BROWSERS: &[&str] = &["chrome", "edge", "firefox", "safari"];
+
+pub fn load(worktree: &Worktree) -> Result<Value> {
+    let settings = MdnSettings::for_worktree(worktree);
+    let path = settings.compat_data.as_deref().unwrap_or(DEFAULT_DATA);
+    let json = worktree
+        .read_text_file(path)
+        .map_err(|_| format!("no compat data at {path}, run `yarn` in the checkout"))?;
+    serde_json::from_str(&json).map_err(|e| format!("invalid {path}: {e}"))
+}
+
+/// BCD keys `symbol` may stand for, most likely first.
+fn candidates(symbol: &str) -> Vec<String> {
+    let symbol = symbol.trim().trim_matches('`');
+    if let Some(element) = symbol.strip_prefix('<') {
+        return vec![format!("html.elements.{}", element.trim_end_matches('>'))];
+    }
+    let name = symbol.trim_end_matches("()");
+    if name.contains('-') && name.chars().all(|c| c.is_ascii_lowercase() || c == '-') {
+        return vec![
+            format!("css.properties.{name}"),
+            format!("css.types.{name}"),
+        ];
+    }
+    if name.contains('-') {
+        return vec![format!("http.headers.{name}")];
+    }
+    let mut keys = vec![
+        format!("api.{name}"),
+        format!("javascript.builtins.{name}"),
+        format!("api._globals.{name}"),
+    ];
+    if let Some((_, member)) = name.rsplit_once('.') {
+        keys.push(format!("api.Window.{member}"));
+    }
+    keys
+}
+
+/// The first candidate key of `symbol` with compat data.
+pub fn resolve<'a>(data: &'a Value, symbol: &str) -> Option<(String, &'a Value)> {
+    candidates(symbol).into_iter().find_map(|key| {
+        let compat = key
+            .split('.')
+            .try_fold(data, |node, part| node.get(part))?
+            .get("__compat")?;
+        Some((key, compat))
+    })
+}
+
+/// Status flags and the first supporting version per major browser.
+pub fn describe(key: &str, compat: &Value) -> String {
+    let mut text = format!("{key}\n");
+    let status = &compat["status"];
+    let flags: Vec<&str> = [
+        ("experimental", "experimental"),
+        ("deprecated", "deprecated"),
+    ]
+    .into_iter()
+    .filter(|(field, _)| status[field].as_bool() == Some(true))
+    .map(|(_, label)| label)
+    .chain((status["standard_track"].as_bool() == Some(false)).then_some("non-standard"))
+    .collect();
+    if !flags.is_empty() {
+        text.push_str(&format!("status: {}\n", flags.join(", ")));
+    }
+    for browser in BROWSERS {
+        // Support is a statement or a list of them, newest first.
+        let support = &compat["support"][browser];
+        let statement = support
+            .as_array()
+            .and_then(|s| s.first())
+            .unwrap_or(support);
+        let version = match &statement["version_added"] {
+            Value::String(version) => version.clone(),
+            Value::Bool(false) => "no".to_string(),
+            _ => "?".to_string(),
+        };
+        text.push_str(&format!("{browser}: {version}\n"));
+    }
+    text
+}
@@ -0,0 +1,73 @@
+//! Running rari inside the project's dev container with `docker exec`, for
+//! setups where the content only exists in the container's volume.
+
+use serde::Deserialize;
+use zed_extension_api::{self as zed, Command, Result, Worktree};
+
+use crate::paths;
+
+/// Files that suggest the project is meant to be worked on in a container.
+const CONTAINER_FILES: &[&str] = &[
+    ".devcontainer/devcontainer.json",
+    ".devcontainer.json",
+    "docker-compose.yml",
+    "compose.yaml",
+];
+
+/// The container to run rari in, configured under `container`.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct ContainerSettings {
+    /// Name or id of the running container.
+    pub name: String,
+    /// rari inside the container, `rari` from its `PATH` by default.
+    pub binary: Option<String>,
+    /// `CONTENT_ROOT` inside the container, by default `files/` of the
+    /// worktree in `/workspaces` like dev containers mount it.
+    pub content_root: Option<String>,
+}
+
+/// The container setup file of the worktree, if it has one.
+pub fn detect(worktree: &Worktree) -> Option<&'static str> {
+    CONTAINER_FILES
+        .iter()
+        .copied()
+        .find(|path| worktree.read_text_file(path).is_ok())
+}
+
+/// `docker exec` of `rari lsp` in the configured container.
+pub fn command(worktree: &Worktree, container: &ContainerSettings) -> Result<Command> {
+    if container.name.is_empty() {
+        return Err("container.name must name the running container".to_string());
+    }
+    let docker = worktree
+        .which("docker")
+        .ok_or("running rari in a container needs docker on the PATH")?;
+    let content_root = container.content_root.clone().unwrap_or_else(|| {
+        let root = worktree.root_path();
+        let folder = root
+            .trim_end_matches('/')
+            .rsplit('/')
+            .next()
+            .unwrap_or_default();
+        format!("/workspaces/{folder}/files")
+    });
+    // Containers run Linux whatever the host.
+    let content_root = paths::normalize(&content_root, zed::Os::Linux);
+    Ok(Command {
+        command: docker,
+        args: vec![
+            "exec".to_string(),
+            "-i".to_string(),
+            "-e".to_string(),
+            format!("CONTENT_ROOT={content_root}"),
+            container.name.clone(),
+            container
+                .binary
+                .clone()
+                .unwrap_or_else(|| "rari".to_string()),
+            "lsp".to_string(),
+        ],
+        env: vec![],
+    })
+}
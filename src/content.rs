@@ -0,0 +1,174 @@
+//! Mapping between MDN URLs, slugs and files in the content tree.
+
+use zed_extension_api::{self as zed, process::Command, Worktree};
+
+use crate::{front_matter::FrontMatter, git, glob, paths, settings::MdnSettings};
+
+pub const MDN_ORIGIN: &str = "https://developer.mozilla.org";
+
+/// Absolute path of the directory rari reads content from: the
+/// `content_root` setting, relative to the worktree, or the checkout's
+/// `files/`.
+pub fn content_root(worktree: &Worktree) -> String {
+    let settings = MdnSettings::for_worktree(worktree);
+    let mut root = match settings.content_root {
+        Some(root) if root.starts_with('/') || root.get(1..2) == Some(":") => root,
+        Some(root) => format!("{}/{root}", worktree.root_path()),
+        None => format!("{}/files", repo_root(worktree)),
+    };
+    if settings.resolve_symlinks.applies_to("content_root") {
+        root = paths::resolve_symlinks(&root);
+    }
+    paths::normalize(&root, zed::current_platform().0)
+}
+
+/// Absolute path of the mdn/content checkout translated pages are compared
+/// with: `en_us_content_path`, relative to the worktree, or the worktree
+/// itself.
+pub fn en_us_repo(worktree: &Worktree, settings: &MdnSettings) -> String {
+    let root = worktree.root_path();
+    let repo = match &settings.en_us_content_path {
+        Some(repo) if repo.starts_with('/') => repo.clone(),
+        Some(repo) => format!("{root}/{repo}"),
+        None => root,
+    };
+    if settings.resolve_symlinks.applies_to("en_us_content_path") {
+        return paths::resolve_symlinks(&repo);
+    }
+    repo
+}
+
+/// An instructive error when the content root doesn't exist, which rari
+/// would only fail on with a cascade of unrelated looking errors.
+pub fn check_content_root(worktree: &Worktree) -> Result<(), String> {
+    if zed::current_platform().0 == zed::Os::Windows {
+        return Ok(());
+    }
+    let root = content_root(worktree);
+    // The extension can't look outside of its work directory itself.
+    let exists = Command::new("test")
+        .args(["-d", &root])
+        .output()
+        .is_ok_and(|output| output.status == Some(0));
+    if exists {
+        return Ok(());
+    }
+    Err(format!(
+        "this does not look like an MDN content checkout: expected a files/ directory \
+         at {root}, or set `content_root` in lsp.mdn-lsp.settings"
+    ))
+}
+
+/// Where the worktree is in the checkout when that's `files/` or below, like
+/// `files/ja/`. Only rari supports this layout, the slash commands and
+/// client-side checks take paths relative to the checkout root.
+pub fn files_prefix(worktree: &Worktree) -> Option<String> {
+    let prefix = git::run(&worktree.root_path(), &["rev-parse", "--show-prefix"]).ok()?;
+    prefix.starts_with("files/").then_some(prefix)
+}
+
+/// Absolute path of the checkout holding the repository level `files/`.
+/// That's usually the worktree itself, but a worktree opened on `files/ja`
+/// of translated-content is two levels below it.
+pub fn repo_root(worktree: &Worktree) -> String {
+    let root = worktree.root_path();
+    let Some(prefix) = files_prefix(worktree) else {
+        return root;
+    };
+    let components: Vec<&str> = prefix.split('/').filter(|c| !c.is_empty()).collect();
+    let mut repo_root = root.trim_end_matches('/');
+    for _ in &components {
+        repo_root = repo_root
+            .rsplit_once('/')
+            .map_or(repo_root, |(parent, _)| parent);
+    }
+    repo_root.to_string()
+}
+
+/// Whether the worktree relative `path` is an MDN document rather than
+/// other Markdown in the project like `CONTRIBUTING.md`.
+pub fn is_document(settings: &MdnSettings, path: &str) -> bool {
+    glob::matches_any(&settings.documents, path)
+}
+
+/// An MDN document address: `/<locale>/docs/<slug>#<fragment>`.
+pub struct DocUrl {
+    pub locale: String,
+    pub slug: String,
+    pub fragment: Option<String>,
+}
+
+impl DocUrl {
+    /// Parses absolute developer.mozilla.org URLs as well as site relative ones.
+    pub fn parse(url: &str) -> Option<Self> {
+        let url = url.trim();
+        let path = url
+            .strip_prefix(MDN_ORIGIN)
+            .or_else(|| url.strip_prefix("http://developer.mozilla.org"))
+            .unwrap_or(url);
+        let (path, fragment) = match path.split_once('#') {
+            Some((path, fragment)) => (path, Some(fragment.to_string())),
+            None => (path, None),
+        };
+        let path = path.split('?').next()?.trim_end_matches('/');
+        let (locale, slug) = path.strip_prefix('/')?.split_once("/docs/")?;
+        if locale.is_empty() || slug.is_empty() {
+            return None;
+        }
+        Some(DocUrl {
+            locale: locale.to_string(),
+            slug: slug.to_string(),
+            fragment,
+        })
+    }
+
+    /// The canonical site relative form used in MDN Markdown links.
+    pub fn relative(&self) -> String {
+        let mut url = format!("/{}/docs/{}", self.locale, self.slug);
+        if let Some(fragment) = &self.fragment {
+            url.push('#');
+            url.push_str(fragment);
+        }
+        url
+    }
+
+    pub fn file_path(&self) -> String {
+        slug_to_file_path(&self.locale, &self.slug)
+    }
+}
+
+/// Worktree relative path of the `index.md` for a slug, following rari's
+/// slug to folder conversion.
+pub fn slug_to_file_path(locale: &str, slug: &str) -> String {
+    let folder = slug
+        .to_lowercase()
+        .replace('*', "_star_")
+        .replace("::", "_doublecolon_")
+        .replace(':', "_colon_")
+        .replace('?', "_question_");
+    format!("files/{}/{folder}/index.md", locale.to_lowercase())
+}
+
+/// Reads and parses the front-matter of the page at `url`, failing when the
+/// page does not exist in the local tree.
+pub fn read_front_matter(worktree: &Worktree, url: &DocUrl) -> Result<FrontMatter, String> {
+    let path = url.file_path();
+    let source = worktree
+        .read_text_file(&path)
+        .map_err(|_| format!("no page for {} in this checkout ({path})", url.relative()))?;
+    FrontMatter::parse(&source).ok_or(format!("{path} has no front-matter"))
+}
+
+/// The lowercase locale folder of a page: `fr` for `files/fr/web/index.md`.
+pub fn locale_of(path: &str) -> Option<&str> {
+    let (locale, _) = path.strip_prefix("files/")?.split_once('/')?;
+    Some(locale)
+}
+
+/// The en-US source of a translated page: `files/fr/web/index.md` becomes
+/// `files/en-us/web/index.md`.
+pub fn en_us_counterpart(path: &str) -> Option<String> {
+    let locale = locale_of(path)?;
+    let rest = &path["files/".len() + locale.len() + 1..];
+    (locale != "en-us").then(|| format!("files/en-us/{rest}"))
+}
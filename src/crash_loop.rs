@@ -0,0 +1,54 @@
+//! Detection of a language server that dies right after every start.
+//!
+//! Zed doesn't tell extensions that a server exited, but it asks for the
+//! command again to restart it, so quick successive starts are suspected
+//! crashes. They only count once a probe run of the server fails, restarting
+//! it by hand in quick succession doesn't.
+
+use std::time::{Duration, Instant};
+
+use zed_extension_api::{process, Command};
+
+/// A restart within this time counts as a crash on launch.
+const FAST_RESTART: Duration = Duration::from_secs(10);
+/// Crashes on launch in a row after which the server isn't started again.
+pub const MAX_FAST_RESTARTS: u32 = 3;
+
+#[derive(Debug, Default)]
+pub struct Launches {
+    last: Option<Instant>,
+    fast_restarts: u32,
+}
+
+impl Launches {
+    /// Records a start and returns whether the server is crash looping. The
+    /// count starts over afterwards, so restarting it by hand tries again.
+    pub fn record(&mut self) -> bool {
+        let now = Instant::now();
+        self.fast_restarts = match self.last {
+            Some(last) if now.duration_since(last) < FAST_RESTART => self.fast_restarts + 1,
+            _ => 0,
+        };
+        self.last = Some(now);
+        if self.fast_restarts >= MAX_FAST_RESTARTS {
+            *self = Launches::default();
+            return true;
+        }
+        false
+    }
+}
+
+/// Runs the server once without a client and returns what it printed to
+/// stderr if it failed. Zed runs it without input, which a healthy server
+/// reads as the end of the session and exits cleanly.
+pub fn probe(command: &Command) -> Option<String> {
+    let output = process::Command::new(&command.command)
+        .args(command.args.iter().cloned())
+        .envs(command.env.iter().cloned())
+        .output();
+    match output {
+        Ok(output) if output.status == Some(0) => None,
+        Ok(output) => Some(String::from_utf8_lossy(&output.stderr).trim().to_string()),
+        Err(e) => Some(e),
+    }
+}
@@ -0,0 +1,51 @@
+use std::{env, fs};
+
+use zed_extension_api::{
+    serde_json::{self, json},
+    Worktree,
+};
+
+use crate::settings::MdnSettings;
+
+const MDN_WORDS: &str = include_str!("../dictionaries/mdn.txt");
+const HARPER_DICT_FILE: &str = "mdn-dictionary.txt";
+
+/// The bundled MDN wordlist merged with the repo-local one configured via
+/// `dictionary`.
+pub fn words(worktree: &Worktree, settings: &MdnSettings) -> Vec<String> {
+    let local = settings
+        .dictionary
+        .as_deref()
+        .and_then(|path| worktree.read_text_file(path).ok())
+        .unwrap_or_default();
+    let mut words: Vec<String> = MDN_WORDS
+        .lines()
+        .chain(local.lines())
+        .map(str::trim)
+        .filter(|word| !word.is_empty() && !word.starts_with('#'))
+        .map(String::from)
+        .collect();
+    words.sort();
+    words.dedup();
+    words
+}
+
+/// Workspace configuration teaching known spellcheckers about MDN vocabulary.
+pub fn spellchecker_configuration(
+    target_language_server_id: &str,
+    worktree: &Worktree,
+) -> Option<serde_json::Value> {
+    let settings = MdnSettings::for_worktree(worktree);
+    match target_language_server_id {
+        "harper-ls" => {
+            // harper only reads dictionaries from disk.
+            fs::write(HARPER_DICT_FILE, words(worktree, &settings).join("\n")).ok()?;
+            let path = env::current_dir().ok()?.join(HARPER_DICT_FILE);
+            Some(json!({ "harper-ls": { "userDictPath": path.to_string_lossy() } }))
+        }
+        "cspell" | "cspell-lsp" => {
+            Some(json!({ "cSpell": { "userWords": words(worktree, &settings) } }))
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,97 @@
+//! Free disk space check before downloading rari, and stripping it after.
+//!
+//! WASI has no `statvfs`, so free space comes from `df` and the asset size
+//! from a `HEAD` request. If either is unknown the check passes.
+
+use std::{env, fs};
+
+use zed_extension_api::{
+    self as zed,
+    http_client::{HttpMethod, HttpRequest, RedirectPolicy},
+    process::Command,
+    Result,
+};
+
+/// Rough ratio of disk needed during install to the size of the archive:
+/// the archive itself plus the extracted binary.
+const INSTALL_SIZE_FACTOR: u64 = 4;
+
+pub fn ensure_space_for(download_url: &str) -> Result<()> {
+    let (Some(asset_size), Some(available)) = (asset_size(download_url), available_space()) else {
+        return Ok(());
+    };
+    let needed = asset_size * INSTALL_SIZE_FACTOR;
+    if available < needed {
+        return Err(format!(
+            "not enough free disk space to install rari: need about {} MB, {} MB available",
+            needed / 1_000_000,
+            available / 1_000_000
+        ));
+    }
+    Ok(())
+}
+
+fn asset_size(url: &str) -> Option<u64> {
+    let response = HttpRequest::builder()
+        .method(HttpMethod::Head)
+        .url(url)
+        .redirect_policy(RedirectPolicy::FollowAll)
+        .build()
+        .ok()?
+        .fetch()
+        .ok()?;
+    response
+        .headers
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+        .and_then(|(_, value)| value.trim().parse().ok())
+}
+
+/// Free bytes in the extension's work directory.
+fn available_space() -> Option<u64> {
+    match zed::current_platform().0 {
+        zed::Os::Mac | zed::Os::Linux => {}
+        zed::Os::Windows => return None,
+    }
+    let dir = env::current_dir().ok()?;
+    let output = Command::new("df")
+        .args(["-Pk", &dir.to_string_lossy()])
+        .output()
+        .ok()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let available_kb: u64 = stdout
+        .lines()
+        .nth(1)?
+        .split_whitespace()
+        .nth(3)?
+        .parse()
+        .ok()?;
+    Some(available_kb * 1024)
+}
+
+/// Removes debug symbols from the binary at the work directory relative
+/// `path` with the system's `strip`, see `strip_binary`. Failures only leave
+/// the binary larger. Not on macOS, where stripping invalidates the code
+/// signature and Apple silicon kills the binary on launch.
+pub fn strip(path: &str) {
+    match zed::current_platform().0 {
+        zed::Os::Linux => {}
+        zed::Os::Mac | zed::Os::Windows => return,
+    }
+    let Ok(dir) = env::current_dir() else {
+        return;
+    };
+    let size = || fs::metadata(path).map(|stat| stat.len() / 1_000_000).ok();
+    let before = size();
+    let stripped = Command::new("strip")
+        .args(["-S", &dir.join(path).to_string_lossy()])
+        .output()
+        .is_ok_and(|output| output.status == Some(0));
+    match (stripped, before, size()) {
+        (true, Some(before), Some(after)) => {
+            println!("mdn: stripped rari from {before} MB to {after} MB")
+        }
+        (true, _, _) => {}
+        (false, _, _) => println!("mdn: could not strip rari, is `strip` installed?"),
+    }
+}
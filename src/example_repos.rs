@@ -0,0 +1,149 @@
+//! Local checkouts of the repositories pages link their code samples from,
+//! like mdn/dom-examples, configured in `example_repos`.
+//!
+//! Links to the repository on GitHub, to its GitHub pages and
+//! `{{EmbedGHLiveSample}}` calls are mapped to files in the checkout, so
+//! they can be opened and checked to still exist.
+
+use std::collections::HashMap;
+
+use zed_extension_api::{process::Command, Worktree};
+
+use crate::{install_dir, macros, markdown, paths, settings::MdnSettings};
+
+/// Owner of the repositories `{{EmbedGHLiveSample}}` paths are in.
+const LIVE_SAMPLE_OWNER: &str = "mdn";
+
+/// A reference from a page into an example repository.
+pub struct Reference {
+    /// Zero based line.
+    pub line: usize,
+    /// Zero based byte column of the link or macro call.
+    pub column: usize,
+    /// Lowercase `owner/name` of the repository.
+    pub repo: String,
+    /// Path in the repository, empty for its root.
+    pub path: String,
+}
+
+/// Where a reference points to in the local checkouts.
+pub enum Target {
+    File(String),
+    /// The checkout exists but doesn't have the file.
+    Missing(String),
+    /// The configured checkout doesn't exist.
+    NoCheckout(String),
+}
+
+/// The configured checkouts by lowercase `owner/name`, as absolute paths.
+/// Relative paths are relative to the worktree, and `~/` is expanded.
+pub fn checkouts(worktree: &Worktree, settings: &MdnSettings) -> HashMap<String, String> {
+    let environment = worktree.shell_env();
+    let platform = zed_extension_api::current_platform().0;
+    settings
+        .example_repos
+        .iter()
+        .filter_map(|(repo, path)| {
+            let path = install_dir::expand_home(path, &environment)?;
+            let path = if path.starts_with('/') {
+                path
+            } else {
+                format!("{}/{path}", worktree.root_path())
+            };
+            Some((repo.to_lowercase(), paths::normalize(&path, platform)))
+        })
+        .collect()
+}
+
+/// The references of `source` into any GitHub repository.
+pub fn references(source: &str) -> Vec<Reference> {
+    let mut references: Vec<Reference> = markdown::links(source)
+        .into_iter()
+        .filter_map(|link| {
+            let (repo, path) = parse_url(&link.url)?;
+            Some(Reference {
+                line: link.line,
+                column: link.column,
+                repo,
+                path,
+            })
+        })
+        .collect();
+    references.extend(
+        macros::calls(source)
+            .into_iter()
+            .filter(|call| call.name.eq_ignore_ascii_case("EmbedGHLiveSample"))
+            .filter_map(|call| {
+                let arg = call.args?.into_iter().next()?;
+                // `dom-examples/web-audio-api/index.html`
+                let arg = arg.trim_matches(|c| c == '"' || c == '\'');
+                let (name, path) = arg.split_once('/').unwrap_or((arg, ""));
+                Some(Reference {
+                    line: call.line,
+                    column: call.column,
+                    repo: format!("{LIVE_SAMPLE_OWNER}/{}", name.to_lowercase()),
+                    path: path.trim_matches('/').to_string(),
+                })
+            }),
+    );
+    references.sort_by_key(|reference| (reference.line, reference.column));
+    references
+}
+
+/// `owner/name` and path of `https://github.com/owner/name/blob/main/path`
+/// and `https://owner.github.io/name/path`.
+fn parse_url(url: &str) -> Option<(String, String)> {
+    let url = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))?;
+    let url = url.split(['#', '?']).next().unwrap_or(url);
+    let (owner, name, path) = if let Some(rest) = url.strip_prefix("github.com/") {
+        let mut parts = rest.splitn(5, '/');
+        let owner = parts.next()?;
+        let name = parts.next()?;
+        let path = match (parts.next(), parts.next()) {
+            (Some("tree" | "blob"), Some(_branch)) => parts.next().unwrap_or_default(),
+            (None | Some(""), _) => "",
+            // Issues, pull requests and such.
+            _ => return None,
+        };
+        (owner, name, path)
+    } else {
+        let (host, rest) = url.split_once('/')?;
+        let owner = host.strip_suffix(".github.io")?;
+        let (name, path) = rest.split_once('/').unwrap_or((rest, ""));
+        (owner, name, path)
+    };
+    if owner.is_empty() || name.is_empty() {
+        return None;
+    }
+    Some((
+        format!("{owner}/{name}").to_lowercase(),
+        path.trim_matches('/').to_string(),
+    ))
+}
+
+/// Where `reference` points in `checkouts`, `None` for repositories without
+/// a configured checkout.
+pub fn resolve(checkouts: &HashMap<String, String>, reference: &Reference) -> Option<Target> {
+    let checkout = checkouts.get(&reference.repo)?;
+    let file = if reference.path.is_empty() {
+        checkout.clone()
+    } else {
+        format!("{checkout}/{}", reference.path)
+    };
+    // The checkouts are outside of the extension's work directory.
+    let exists = |path: &str| {
+        Command::new("test")
+            .args(["-e", path])
+            .output()
+            .is_ok_and(|output| output.status == Some(0))
+    };
+    Some(if exists(&file) {
+        Target::File(file)
+    } else if exists(checkout) {
+        Target::Missing(file)
+    } else {
+        Target::NoCheckout(checkout.clone())
+    })
+}
@@ -0,0 +1,34 @@
+//! Opt-in `rari-path.env` in the extension's work directory, so terminal
+//! tasks and git hooks can `source` it and run the same rari as the editor.
+
+use std::{env, fs, path::Path};
+
+const EXPORT_FILE: &str = "rari-path.env";
+
+pub fn write(binary_path: &str) {
+    let Ok(work_dir) = env::current_dir() else {
+        return;
+    };
+    let absolute = work_dir.join(binary_path);
+    let mut contents = format!(
+        "RARI_BINARY_PATH={}\n",
+        shell_quote(&absolute.to_string_lossy())
+    );
+    if let Some(version) = managed_version(binary_path) {
+        contents.push_str(&format!("RARI_VERSION={}\n", shell_quote(version)));
+    }
+    fs::write(EXPORT_FILE, contents).ok();
+}
+
+/// The version of a managed install, from its `rari-<version>/` directory.
+pub fn managed_version(binary_path: &str) -> Option<&str> {
+    Path::new(binary_path)
+        .parent()?
+        .file_name()?
+        .to_str()?
+        .strip_prefix("rari-")
+}
+
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
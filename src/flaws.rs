@@ -0,0 +1,60 @@
+//! Longer explanations of rari's diagnostics, from `data/flaws.json`.
+//!
+//! rari reports flaws like "ill-cased link" in a few words, which means
+//! little to new contributors. Zed has no way for extensions to add hover
+//! content or related information to another server's diagnostics, so the
+//! explanations are looked up with `/mdn-explain`.
+
+use serde::Deserialize;
+use zed_extension_api::serde_json;
+
+const FLAWS: &str = include_str!("../data/flaws.json");
+
+#[derive(Debug, Deserialize)]
+pub struct Flaw {
+    pub code: String,
+    pub title: String,
+    /// Lowercase fragments of rari's messages for this flaw.
+    pub messages: Vec<String>,
+    pub explanation: String,
+    /// The contributor docs covering the flaw.
+    pub docs: String,
+}
+
+impl Flaw {
+    pub fn describe(&self) -> String {
+        format!(
+            "{} ({})\n\n{}\n\nSee {}\n",
+            self.title, self.code, self.explanation, self.docs
+        )
+    }
+}
+
+pub fn all() -> Vec<Flaw> {
+    serde_json::from_str(FLAWS).expect("bundled flaws are valid")
+}
+
+/// The flaw for a code like `ill-cased-link` or `IllCasedLink`, or else for
+/// a diagnostic message copied from the problems panel. The first flaw
+/// whose message fragment occurs wins, so the specific ones come first.
+pub fn find(query: &str) -> Option<Flaw> {
+    let normalize = |text: &str| {
+        text.chars()
+            .filter(char::is_ascii_alphanumeric)
+            .collect::<String>()
+            .to_lowercase()
+    };
+    let code = normalize(query);
+    let message = query.to_lowercase();
+    let flaws = all();
+    let by_code = flaws.iter().position(|flaw| normalize(&flaw.code) == code);
+    let by_message = || {
+        flaws.iter().position(|flaw| {
+            flaw.messages
+                .iter()
+                .any(|fragment| message.contains(fragment.as_str()))
+        })
+    };
+    let position = by_code.or_else(by_message)?;
+    flaws.into_iter().nth(position)
+}
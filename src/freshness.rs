@@ -0,0 +1,32 @@
+//! Warns when the checkout is far behind upstream, where macros and sidebars
+//! resolve against outdated pages.
+
+use zed_extension_api::Worktree;
+
+use crate::{git, settings::MdnSettings, state};
+
+const UPSTREAM: &str = "origin/main";
+
+/// A warning when the newest upstream commit in `HEAD` is older than
+/// `stale_after_days`. Only fetches when `fetch_upstream` allows it,
+/// otherwise `origin/main` is as recent as the last fetch.
+pub fn check(worktree: &Worktree, settings: &MdnSettings) -> Option<String> {
+    if settings.stale_after_days == 0 {
+        return None;
+    }
+    let root = worktree.root_path();
+    if settings.fetch_upstream {
+        git::run(&root, &["fetch", "--quiet", "origin", "main"])
+            .inspect_err(|e| println!("mdn: {e}"))
+            .ok();
+    }
+    let base = git::run(&root, &["merge-base", UPSTREAM, "HEAD"]).ok()?;
+    let time: u64 = git::run(&root, &["log", "-1", "--format=%ct", &base])
+        .ok()?
+        .parse()
+        .ok()?;
+    let days = state::now().saturating_sub(time) / (24 * 60 * 60);
+    (days >= settings.stale_after_days).then(|| {
+        format!("this checkout is based on {UPSTREAM} from {days} days ago, consider pulling")
+    })
+}
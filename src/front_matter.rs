@@ -0,0 +1,237 @@
+//! Just enough YAML to read and rewrite MDN front-matter.
+//!
+//! Front-matter on MDN is flat `key: value` pairs plus a few nested blocks
+//! (`l10n`, `browser-compat` lists). Nested content is kept verbatim with its
+//! parent key so rewrites never touch values.
+
+/// The key order MDN's front-matter linter expects. Other keys go last, in
+/// the order they are in.
+pub const KEY_ORDER: &[&str] = &[
+    "title",
+    "short-title",
+    "slug",
+    "page-type",
+    "status",
+    "browser-compat",
+    "spec-urls",
+    "sidebar",
+    "l10n",
+];
+
+pub struct Entry {
+    pub key: String,
+    /// Raw value of the first line, without the key.
+    pub value: String,
+    /// Continuation lines belonging to this key (nested maps, lists).
+    pub nested: Vec<String>,
+}
+
+pub struct FrontMatter {
+    pub entries: Vec<Entry>,
+}
+
+impl FrontMatter {
+    pub fn parse(source: &str) -> Option<Self> {
+        let mut lines = source.lines();
+        if lines.next()?.trim_end() != "---" {
+            return None;
+        }
+        let mut entries: Vec<Entry> = vec![];
+        for text in lines {
+            if text.trim_end() == "---" {
+                return Some(FrontMatter { entries });
+            }
+            let is_nested = text.starts_with([' ', '\t', '-']) || text.trim().is_empty();
+            match (is_nested, entries.last_mut()) {
+                (true, Some(entry)) => entry.nested.push(text.to_string()),
+                _ => {
+                    let (key, value) = text.split_once(':')?;
+                    entries.push(Entry {
+                        key: key.trim().to_string(),
+                        value: value.trim().to_string(),
+                        nested: vec![],
+                    });
+                }
+            }
+        }
+        None
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Entry> {
+        self.entries.iter().find(|entry| entry.key == key)
+    }
+
+    /// The unquoted scalar value of `key`.
+    pub fn value(&self, key: &str) -> Option<String> {
+        self.get(key).map(|entry| unquote(&entry.value))
+    }
+
+    pub fn title(&self) -> Option<String> {
+        self.value("title")
+    }
+
+    pub fn page_type(&self) -> Option<String> {
+        self.value("page-type")
+    }
+
+    /// Zero based line of the entry at `index` in the document.
+    pub fn line_of(&self, index: usize) -> usize {
+        1 + self.entries[..index]
+            .iter()
+            .map(|entry| 1 + entry.nested.len())
+            .sum::<usize>()
+    }
+
+    /// Index of the first entry that is out of [`KEY_ORDER`].
+    pub fn first_misordered(&self) -> Option<usize> {
+        let ranks: Vec<usize> = self.entries.iter().map(|entry| rank(&entry.key)).collect();
+        ranks
+            .windows(2)
+            .position(|pair| pair[0] > pair[1])
+            .map(|i| i + 1)
+    }
+
+    /// Sets the scalar value of `key`. New keys go after the last key that
+    /// comes before them in [`KEY_ORDER`].
+    pub fn set(&mut self, key: &str, value: &str) {
+        if let Some(entry) = self.entries.iter_mut().find(|entry| entry.key == key) {
+            entry.value = value.to_string();
+            entry.nested.clear();
+            return;
+        }
+        let index = self
+            .entries
+            .iter()
+            .rposition(|entry| rank(&entry.key) <= rank(key))
+            .map_or(0, |i| i + 1);
+        self.entries.insert(
+            index,
+            Entry {
+                key: key.to_string(),
+                value: value.to_string(),
+                nested: vec![],
+            },
+        );
+    }
+
+    /// Removes `key` and its nested lines, returning whether it was there.
+    pub fn remove(&mut self, key: &str) -> bool {
+        let len = self.entries.len();
+        self.entries.retain(|entry| entry.key != key);
+        self.entries.len() != len
+    }
+
+    /// The front-matter block with values and nested lines untouched.
+    pub fn to_block(&self) -> String {
+        block_of(self.entries.iter())
+    }
+
+    /// The front-matter block with entries in [`KEY_ORDER`], values and
+    /// nested lines untouched.
+    pub fn to_sorted_block(&self) -> String {
+        let mut entries: Vec<&Entry> = self.entries.iter().collect();
+        entries.sort_by_key(|entry| rank(&entry.key));
+        block_of(entries)
+    }
+}
+
+fn block_of<'a>(entries: impl IntoIterator<Item = &'a Entry>) -> String {
+    let mut block = "---\n".to_string();
+    for entry in entries {
+        if entry.value.is_empty() {
+            block.push_str(&format!("{}:\n", entry.key));
+        } else {
+            block.push_str(&format!("{}: {}\n", entry.key, entry.value));
+        }
+        for line in &entry.nested {
+            block.push_str(&format!("{line}\n"));
+        }
+    }
+    block.push_str("---");
+    block
+}
+
+fn rank(key: &str) -> usize {
+    KEY_ORDER
+        .iter()
+        .position(|known| *known == key)
+        .unwrap_or(KEY_ORDER.len())
+}
+
+pub fn unquote(value: &str) -> String {
+    let value = value.trim();
+    for quote in ['"', '\''] {
+        if let Some(inner) = value
+            .strip_prefix(quote)
+            .and_then(|rest| rest.strip_suffix(quote))
+        {
+            return inner.to_string();
+        }
+    }
+    value.to_string()
+}
+
+/// Sets `parent.key` in the front-matter of `source`, creating the parent map
+/// when needed. Returns the whole document with every other line untouched.
+pub fn set_nested_value(source: &str, parent: &str, key: &str, value: &str) -> Option<String> {
+    let mut lines: Vec<String> = source.lines().map(String::from).collect();
+    if lines.first()?.trim_end() != "---" {
+        return None;
+    }
+    let end = lines.iter().skip(1).position(|l| l.trim_end() == "---")? + 1;
+    let parent_prefix = format!("{parent}:");
+    let new_line = format!("  {key}: {value}");
+    match (1..end).find(|&i| lines[i].trim_end() == parent_prefix) {
+        Some(parent_line) => {
+            let children = (parent_line + 1..end)
+                .take_while(|&i| lines[i].starts_with([' ', '\t']))
+                .collect::<Vec<_>>();
+            let key_prefix = format!("{key}:");
+            match children
+                .iter()
+                .find(|&&i| lines[i].trim_start().starts_with(&key_prefix))
+            {
+                Some(&i) => lines[i] = new_line,
+                None => lines.insert(parent_line + 1, new_line),
+            }
+        }
+        None => {
+            lines.insert(end, new_line);
+            lines.insert(end, parent_prefix);
+        }
+    }
+    let mut out = lines.join("\n");
+    if source.ends_with('\n') {
+        out.push('\n');
+    }
+    Some(out)
+}
+
+/// Inserts the top level `key: value` right after the entry of `after` (or
+/// at the end of the front-matter) and returns the whole document.
+pub fn insert_after(source: &str, after: &str, key: &str, value: &str) -> Option<String> {
+    let front_matter = FrontMatter::parse(source)?;
+    let line = match front_matter
+        .entries
+        .iter()
+        .position(|entry| entry.key == after)
+    {
+        Some(index) => front_matter.line_of(index + 1),
+        None => front_matter.line_of(front_matter.entries.len()),
+    };
+    let mut lines: Vec<&str> = source.lines().collect();
+    let new_line = format!("{key}: {value}");
+    lines.insert(line, &new_line);
+    let mut out = lines.join("\n");
+    if source.ends_with('\n') {
+        out.push('\n');
+    }
+    Some(out)
+}
+
+/// The front-matter block of `source`, including its delimiters.
+pub fn block(source: &str) -> Option<&str> {
+    let rest = source.strip_prefix("---\n")?;
+    let end = rest.find("\n---")? + "---\n".len() + "\n---".len();
+    Some(&source[..end])
+}
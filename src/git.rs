@@ -0,0 +1,73 @@
+//! Thin wrapper around the `git` executable.
+
+use zed_extension_api::{process::Command, Result, Worktree};
+
+/// Runs `git -C <repo> <args>` and returns trimmed stdout.
+pub fn run(repo: &str, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(repo)
+        .args(args.iter().copied())
+        .output()?;
+    if output.status != Some(0) {
+        return Err(format!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// The checked out branch, or the commit of a detached HEAD, from
+/// `.git/HEAD` of the worktree. Reading it spawns no `git`, so it is cheap
+/// enough to check on every `/mdn-status`. `None` where `.git` isn't a
+/// directory at the root of the worktree.
+pub fn checked_out(worktree: &Worktree) -> Option<String> {
+    let head = worktree.read_text_file(".git/HEAD").ok()?;
+    let head = head.trim();
+    match head.strip_prefix("ref: ") {
+        Some(reference) => Some(
+            reference
+                .strip_prefix("refs/heads/")
+                .unwrap_or(reference)
+                .to_string(),
+        ),
+        None => head.get(..7).map(String::from),
+    }
+}
+
+/// The value of `key` in git's config for `repo`, `None` when unset.
+pub fn config(repo: &str, key: &str) -> Option<String> {
+    run(repo, &["config", "--get", key])
+        .ok()
+        .filter(|value| !value.is_empty())
+}
+
+/// Hash of the last commit touching `path`.
+pub fn last_commit(repo: &str, path: &str) -> Result<String> {
+    let commit = run(repo, &["log", "-1", "--format=%H", "--", path])?;
+    if commit.is_empty() {
+        return Err(format!("{path} has no history in {repo}"));
+    }
+    Ok(commit)
+}
+
+/// Paths changed since `repo` branched off `base`, including uncommitted and
+/// untracked changes but not deletions.
+pub fn changed_files(repo: &str, base: &str) -> Result<Vec<String>> {
+    let merge_base = run(repo, &["merge-base", base, "HEAD"])?;
+    let changed = run(
+        repo,
+        &["diff", "--name-only", "--diff-filter=d", &merge_base],
+    )?;
+    let untracked = run(repo, &["ls-files", "--others", "--exclude-standard"])?;
+    let mut files: Vec<String> = changed
+        .lines()
+        .chain(untracked.lines())
+        .map(String::from)
+        .collect();
+    files.sort();
+    files.dedup();
+    Ok(files)
+}
@@ -0,0 +1,31 @@
+//! Path globs as used in settings: `*` and `?` stay within one path segment,
+//! `**` spans segments.
+
+pub fn matches(pattern: &str, path: &str) -> bool {
+    matches_bytes(pattern.as_bytes(), path.as_bytes())
+}
+
+pub fn matches_any(patterns: &[String], path: &str) -> bool {
+    patterns.iter().any(|pattern| matches(pattern, path))
+}
+
+fn matches_bytes(pattern: &[u8], path: &[u8]) -> bool {
+    match pattern {
+        [] => path.is_empty(),
+        [b'*', b'*', b'/', rest @ ..] => {
+            matches_bytes(rest, path)
+                || path
+                    .iter()
+                    .enumerate()
+                    .any(|(i, &b)| b == b'/' && matches_bytes(rest, &path[i + 1..]))
+        }
+        [b'*', b'*', rest @ ..] => (0..=path.len()).any(|i| matches_bytes(rest, &path[i..])),
+        [b'*', rest @ ..] => (0..=path.len())
+            .take_while(|&i| i == 0 || path[i - 1] != b'/')
+            .any(|i| matches_bytes(rest, &path[i..])),
+        [b'?', rest @ ..] => {
+            matches!(path.first(), Some(&b) if b != b'/') && matches_bytes(rest, &path[1..])
+        }
+        [c, rest @ ..] => path.first() == Some(c) && matches_bytes(rest, &path[1..]),
+    }
+}
@@ -0,0 +1,133 @@
+//! Glossary terms used in prose without a `{{Glossary}}` link.
+//!
+//! The style guide asks for the first occurrence of a term on a page to link
+//! to its glossary entry. Terms come from the folder names below
+//! `files/en-us/glossary/`, so they are lowercase with `_` for spaces.
+
+use crate::{front_matter::FrontMatter, index::ContentIndex, markdown};
+
+/// Terms shorter than this match too much ordinary prose.
+const MIN_TERM_LENGTH: usize = 3;
+
+/// Single words shorter than this, like "node" or "cache", are ordinary
+/// prose as often as they are the term, so they only match written in
+/// capitals like the acronyms "API" and "DOM".
+const SHORT_TERM_LENGTH: usize = 7;
+
+/// An unlinked occurrence of a glossary term.
+pub struct Suggestion {
+    /// Zero based line.
+    pub line: usize,
+    /// Zero based byte column.
+    pub column: usize,
+    /// The term as written on the page.
+    pub text: String,
+}
+
+impl Suggestion {
+    pub fn replacement(&self) -> String {
+        format!("{{{{Glossary(\"{}\")}}}}", self.text)
+    }
+}
+
+/// Glossary terms of the content tree, longest first so "Same-origin policy"
+/// wins over "origin".
+pub fn terms(index: &ContentIndex) -> Vec<String> {
+    let mut terms: Vec<String> = index
+        .en_us_slugs()
+        .filter_map(|slug| slug.strip_prefix("glossary/"))
+        .filter(|term| !term.contains('/') && term.len() >= MIN_TERM_LENGTH)
+        .map(|term| term.replace('_', " "))
+        .collect();
+    terms.sort_by_key(|term| std::cmp::Reverse(term.len()));
+    terms
+}
+
+/// The first unlinked occurrence of each term in `source`. Terms linked
+/// anywhere on the page, and the page's own term, are skipped.
+pub fn suggest(source: &str, terms: &[String]) -> Vec<Suggestion> {
+    let lowercase = source.to_lowercase();
+    let own_slug = FrontMatter::parse(source)
+        .and_then(|front_matter| front_matter.value("slug"))
+        .map(|slug| slug.to_lowercase());
+    let lines: Vec<markdown::ProseLine> = markdown::prose_lines(source)
+        .filter(|prose| !prose.text.trim_start().starts_with('#'))
+        .collect();
+    // Byte ranges already taken, per line, so terms don't overlap.
+    let mut taken: Vec<(usize, usize, usize)> = lines
+        .iter()
+        .flat_map(|prose| {
+            excluded_spans(&prose.text)
+                .into_iter()
+                .map(move |(start, end)| (prose.line, start, end))
+        })
+        .collect();
+    let mut suggestions = vec![];
+    for term in terms {
+        let slug = term.replace(' ', "_");
+        if own_slug.as_deref() == Some(&format!("glossary/{slug}"))
+            || lowercase.contains(&format!("glossary(\"{term}"))
+            || lowercase.contains(&format!("glossary('{term}"))
+            || lowercase.contains(&format!("/glossary/{slug}"))
+        {
+            continue;
+        }
+        let short = !term.contains([' ', '-']) && term.len() < SHORT_TERM_LENGTH;
+        let found = lines.iter().find_map(|prose| {
+            markdown::find_word(&prose.text, term)
+                .into_iter()
+                .find(|&start| {
+                    let end = start + term.len();
+                    let written = &prose.text[start..end];
+                    (!short || !written.chars().any(char::is_lowercase))
+                        && !taken
+                            .iter()
+                            .any(|&(line, s, e)| line == prose.line && start < e && s < end)
+                })
+                .map(|start| (prose, start))
+        });
+        if let Some((prose, start)) = found {
+            let end = start + term.len();
+            taken.push((prose.line, start, end));
+            suggestions.push(Suggestion {
+                line: prose.line,
+                column: start,
+                text: prose.text[start..end].to_string(),
+            });
+        }
+    }
+    suggestions.sort_by_key(|suggestion| (suggestion.line, suggestion.column));
+    suggestions
+}
+
+/// Macro calls and links, where no term may be wrapped.
+fn excluded_spans(text: &str) -> Vec<(usize, usize)> {
+    let mut spans = vec![];
+    for (open, close) in [("{{", "}}"), ("[", ")"), ("<", ">")] {
+        let mut offset = 0;
+        while let Some(start) = text[offset..].find(open).map(|i| offset + i) {
+            let Some(end) = text[start..].find(close).map(|i| start + i + close.len()) else {
+                break;
+            };
+            spans.push((start, end));
+            offset = end;
+        }
+    }
+    spans
+}
+
+/// Lines of `source` with the suggested terms wrapped, as zero based line
+/// number and new text.
+pub fn rewrite(source: &str, suggestions: &[Suggestion]) -> Vec<(usize, String)> {
+    markdown::replace_in_lines(
+        source,
+        suggestions.iter().map(|suggestion| {
+            let end = suggestion.column + suggestion.text.len();
+            (
+                suggestion.line,
+                suggestion.column..end,
+                suggestion.replacement(),
+            )
+        }),
+    )
+}
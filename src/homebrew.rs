@@ -0,0 +1,25 @@
+//! rari installed with Homebrew on macOS.
+//!
+//! Apps started from the Dock or Finder inherit launchd's minimal `PATH`
+//! without Homebrew's `bin` directories, so a `brew install rari` is invisible
+//! to `which` unless Zed was started from a terminal.
+
+use std::collections::HashMap;
+
+use crate::{
+    install_dir,
+    status::{self, BinarySource},
+};
+
+/// `bin` directories of the Apple silicon and Intel prefixes.
+const BIN_DIRS: &[&str] = &["/opt/homebrew/bin", "/usr/local/bin"];
+
+/// The first Homebrew rari that is recent enough, see
+/// [`status::meets_minimum`] for `checked`.
+pub fn resolve(checked: &mut HashMap<String, bool>) -> Option<String> {
+    BIN_DIRS
+        .iter()
+        .map(|dir| format!("{dir}/rari"))
+        .filter(|path| install_dir::is_file(path))
+        .find(|path| status::meets_minimum(path, BinarySource::Homebrew, checked))
+}
@@ -0,0 +1,84 @@
+//! The extension-side index of the content tree.
+//!
+//! Traversal honors `.gitignore` by asking git for the file list, so scratch
+//! directories in forks stay out. Outside of git (or without git) we fall
+//! back to `find`. Either way [`EXCLUDED`] is skipped.
+
+use zed_extension_api::{process::Command, Worktree};
+
+use crate::{git, glob};
+
+/// Never indexed, whatever the ignore files say.
+const EXCLUDED: &[&str] = &[
+    "**/node_modules/**",
+    "build/**",
+    "**/.git/**",
+    "**/target/**",
+];
+
+pub struct ContentIndex {
+    /// Worktree relative paths of all Markdown files below `files/`.
+    pub files: Vec<String>,
+    /// Worktree relative paths of the sidebar definitions.
+    pub sidebars: Vec<String>,
+}
+
+impl ContentIndex {
+    pub fn build(worktree: &Worktree) -> Self {
+        let root = worktree.root_path();
+        let files = git::run(
+            &root,
+            &[
+                "ls-files",
+                "--cached",
+                "--others",
+                "--exclude-standard",
+                "--",
+                "files/*.md",
+            ],
+        )
+        .ok()
+        .filter(|files| !files.is_empty())
+        .map(|files| files.lines().map(String::from).collect())
+        .unwrap_or_else(|| find_markdown(&root));
+        let mut files: Vec<String> = files
+            .into_iter()
+            .filter(|file| !EXCLUDED.iter().any(|pattern| glob::matches(pattern, file)))
+            .collect();
+        files.sort();
+        let sidebars = git::run(&root, &["ls-files", "--", "files/sidebars/*.yaml"])
+            .map(|sidebars| sidebars.lines().map(String::from).collect())
+            .unwrap_or_default();
+        ContentIndex { files, sidebars }
+    }
+
+    /// Lowercase en-US slugs, as derived from the folder names. See
+    /// [`crate::content::read_front_matter`] for the properly cased slug.
+    pub fn en_us_slugs(&self) -> impl Iterator<Item = &str> {
+        self.files
+            .iter()
+            .filter_map(|file| file.strip_prefix("files/en-us/")?.strip_suffix("/index.md"))
+    }
+}
+
+fn find_markdown(root: &str) -> Vec<String> {
+    let Ok(output) = Command::new("find")
+        .args([
+            &format!("{root}/files"),
+            "-name",
+            "node_modules",
+            "-prune",
+            "-o",
+        ])
+        .args(["-name", "*.md", "-type", "f", "-print"])
+        .output()
+    else {
+        return vec![];
+    };
+    let prefix = format!("{root}/");
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|file| file.strip_prefix(&prefix))
+        .map(String::from)
+        .collect()
+}
@@ -0,0 +1,12 @@
+//! Initialization options handed to `rari lsp`.
+//!
+//! User supplied `lsp.mdn-lsp.initialization_options` are passed through.
+
+use zed_extension_api::{serde_json::Value, Worktree};
+
+use crate::settings;
+
+pub fn options(worktree: &Worktree) -> Option<Value> {
+    settings::lsp_settings(worktree)
+        .and_then(|(_, lsp_settings)| lsp_settings.initialization_options)
+}
@@ -0,0 +1,119 @@
+//! Managed rari installs outside of the extension's work directory, for
+//! small home partitions or editor state that gets wiped regularly.
+//!
+//! Extensions can only write to their work directory, so rari is downloaded
+//! there as usual and then moved with `mv`.
+
+use std::env;
+
+use zed_extension_api::{process::Command, Result};
+
+use crate::settings::MdnSettings;
+
+/// Below the configured directory, so removing old installs only ever
+/// touches directories the extension created, even for `install_dir: "~"`.
+const SUBDIR: &str = "mdn-content-zed";
+
+/// `find` arguments after the directory selecting the installs in it, which
+/// are only ever removed through [`remove`].
+const INSTALLS: [&str; 6] = ["-mindepth", "1", "-maxdepth", "1", "-name", "rari-*"];
+
+/// The absolute directory managed installs go to: `mdn-content-zed` in
+/// `$XDG_CACHE_HOME` (`~/.cache` by default) when `install_dir` is `xdg`, or
+/// in the path of `install_dir` where a leading `~` is the home directory.
+pub fn resolve(settings: &MdnSettings, environment: &[(String, String)]) -> Option<String> {
+    let setting = settings.install_dir.as_deref()?.trim_end_matches('/');
+    let dir = match setting {
+        "xdg" => match var(environment, "XDG_CACHE_HOME") {
+            Some(cache) => cache,
+            None => format!("{}/.cache", var(environment, "HOME")?),
+        },
+        _ => expand_home(setting, environment)?,
+    };
+    if !dir.starts_with('/') {
+        println!("mdn: ignoring install_dir {setting}, it must be an absolute path");
+        return None;
+    }
+    Some(format!("{}/{SUBDIR}", dir.trim_end_matches('/')))
+}
+
+fn var(environment: &[(String, String)], name: &str) -> Option<String> {
+    environment
+        .iter()
+        .find(|(key, value)| key == name && !value.is_empty())
+        .map(|(_, value)| value.clone())
+}
+
+/// `path` with a leading `~` replaced by the home directory.
+pub fn expand_home(path: &str, environment: &[(String, String)]) -> Option<String> {
+    if path == "~" {
+        return var(environment, "HOME");
+    }
+    match path.strip_prefix("~/") {
+        Some(rest) => Some(format!("{}/{rest}", var(environment, "HOME")?)),
+        None => Some(path.to_string()),
+    }
+}
+
+/// Whether there is a file at the absolute `path`, which the extension can't
+/// look at directly.
+pub fn is_file(path: &str) -> bool {
+    Command::new("test")
+        .args(["-f", path])
+        .output()
+        .is_ok_and(|output| output.status == Some(0))
+}
+
+/// The rari binary named `binary` installed in `dir`.
+pub fn find(dir: &str, binary: &str) -> Option<String> {
+    let output = Command::new("find")
+        .args([dir, "-mindepth", "2", "-maxdepth", "2", "-type", "f"])
+        .args(["-path", &format!("{dir}/rari-*/{binary}")])
+        .output()
+        .ok()?;
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .next()
+        .map(String::from)
+}
+
+/// Moves the install in `version_dir` of the work directory to `dir`,
+/// replacing a leftover of the same version.
+pub fn relocate(version_dir: &str, dir: &str) -> Result<()> {
+    let work_dir = env::current_dir().map_err(|e| format!("no work directory: {e}"))?;
+    let source = work_dir.join(version_dir).to_string_lossy().to_string();
+    let target = format!("{dir}/{version_dir}");
+    let run = |program: &str, args: &[&str]| -> Result<()> {
+        let output = Command::new(program).args(args.iter().copied()).output()?;
+        if output.status != Some(0) {
+            return Err(format!(
+                "failed to move rari to {dir}: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        Ok(())
+    };
+    run("mkdir", &["-p", dir])?;
+    remove(dir, &["-name", version_dir]);
+    run("mv", &[&source, &target])?;
+    println!("mdn: moved {version_dir} to {dir}");
+    Ok(())
+}
+
+/// Removes the installs in `dir` other than the version directory `keep`.
+/// Callers hold the `gc` lock, see [`crate::lock`].
+pub fn remove_other_installs(dir: &str, keep: &str) {
+    remove(dir, &["!", "-name", keep]);
+}
+
+/// Removes the installs in `dir` that also match the `find` expression
+/// `filter`.
+fn remove(dir: &str, filter: &[&str]) {
+    Command::new("find")
+        .arg(dir)
+        .args(INSTALLS)
+        .args(filter.iter().copied())
+        .args(["-exec", "rm", "-rf", "{}", "+"])
+        .output()
+        .ok();
+}
@@ -0,0 +1,419 @@
+//! Native tests of the installer against recorded GitHub responses and
+//! release archives in `tests/fixtures/installer`, run with
+//! `cargo test --features installer-tests`.
+//!
+//! Zed's host functions only exist in the wasm build, so these cover the
+//! installer's own logic: asset resolution and its fallbacks, unpacking,
+//! checksums and collecting old installs. The work directory is process
+//! wide, so tests touching it take turns through [`WorkDir`].
+
+use std::{
+    env,
+    fs::{self, File},
+    path::PathBuf,
+    process::Command,
+    sync::{Mutex, MutexGuard},
+    time::UNIX_EPOCH,
+};
+
+use zed_extension_api::{
+    serde_json, Architecture, DownloadedFileType, GithubRelease, GithubReleaseAsset, Os,
+};
+
+use crate::{
+    assets::{self, AssetOverride},
+    local_archive, lock, managed_install, remove_other_installs, sha256, verify_checksum,
+};
+
+const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/installer");
+
+/// The recorded response of the GitHub releases API, as
+/// `zed::latest_github_release` hands it to the extension.
+fn release(fixture: &str) -> GithubRelease {
+    let json = fs::read_to_string(format!("{FIXTURES}/{fixture}")).unwrap();
+    let release: serde_json::Value = serde_json::from_str(&json).unwrap();
+    GithubRelease {
+        version: release["tag_name"].as_str().unwrap().to_string(),
+        assets: release["assets"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|asset| GithubReleaseAsset {
+                name: asset["name"].as_str().unwrap().to_string(),
+                download_url: asset["browser_download_url"].as_str().unwrap().to_string(),
+            })
+            .collect(),
+    }
+}
+
+const PLATFORMS: &[(Architecture, Os)] = &[
+    (Architecture::Aarch64, Os::Mac),
+    (Architecture::Aarch64, Os::Linux),
+    (Architecture::Aarch64, Os::Windows),
+    (Architecture::X8664, Os::Mac),
+    (Architecture::X8664, Os::Linux),
+    (Architecture::X8664, Os::Windows),
+];
+
+/// A fresh work directory for the duration of a test.
+struct WorkDir {
+    path: PathBuf,
+    previous: PathBuf,
+    _turn: MutexGuard<'static, ()>,
+}
+
+static WORK_DIR: Mutex<()> = Mutex::new(());
+
+impl WorkDir {
+    fn new(name: &str) -> WorkDir {
+        let turn = WORK_DIR
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let path = env::temp_dir().join(format!("mdn-installer-{name}-{}", std::process::id()));
+        fs::remove_dir_all(&path).ok();
+        fs::create_dir_all(&path).unwrap();
+        let previous = env::current_dir().unwrap();
+        env::set_current_dir(&path).unwrap();
+        WorkDir {
+            path,
+            previous,
+            _turn: turn,
+        }
+    }
+
+    fn install(&self, version_dir: &str, binary: &str) {
+        fs::create_dir_all(version_dir).unwrap();
+        fs::write(format!("{version_dir}/{binary}"), "rari").unwrap();
+    }
+
+    fn entries(&self) -> Vec<String> {
+        let mut entries: Vec<String> = fs::read_dir(".")
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        entries.sort();
+        entries
+    }
+
+    /// Unpacks a fixture archive like `local_archive::extract`, with
+    /// `std::process` in place of Zed's process API.
+    fn extract(&self, archive: &str, version_dir: &str) -> bool {
+        let archive = format!("{FIXTURES}/{archive}");
+        let dir = self.path.join(version_dir).to_string_lossy().to_string();
+        fs::create_dir_all(&dir).unwrap();
+        let (program, args, prints_binary) = local_archive::unpack_command(&archive, &dir);
+        let Ok(output) = Command::new(program).args(args).output() else {
+            return false;
+        };
+        if prints_binary {
+            fs::write(format!("{dir}/rari"), output.stdout).unwrap();
+        }
+        output.status.success()
+    }
+}
+
+impl Drop for WorkDir {
+    fn drop(&mut self) {
+        env::set_current_dir(&self.previous).ok();
+        fs::remove_dir_all(&self.path).ok();
+    }
+}
+
+fn recorded_checksum() -> String {
+    let sums = fs::read_to_string(format!("{FIXTURES}/rari.sha256")).unwrap();
+    sums.split_whitespace().next().unwrap().to_string()
+}
+
+#[test]
+fn finds_the_official_asset_of_every_platform() {
+    let release = release("release-v0.1.30.json");
+    for &(arch, platform) in PLATFORMS {
+        let expected = assets::expected_name(arch, platform).unwrap();
+        let asset = assets::find(release.assets.clone(), arch, platform).unwrap();
+        assert_eq!(asset.name, expected);
+        assert_eq!(
+            asset.download_url,
+            format!("https://github.com/mdn/rari/releases/download/v0.1.30/{expected}")
+        );
+    }
+}
+
+#[test]
+fn prefers_musl_over_gnu_linux_builds() {
+    let release = release("release-v0.1.30.json");
+    let renamed: Vec<GithubReleaseAsset> = release
+        .assets
+        .into_iter()
+        .filter(|asset| asset.name != "rari-x86_64-unknown-linux-musl.tar.gz")
+        .chain([GithubReleaseAsset {
+            name: "rari-x86_64-linux-musl.tar.gz".to_string(),
+            download_url: "https://example.com/rari-x86_64-linux-musl.tar.gz".to_string(),
+        }])
+        .collect();
+    let asset = assets::find(renamed, Architecture::X8664, Os::Linux).unwrap();
+    assert_eq!(asset.name, "rari-x86_64-linux-musl.tar.gz");
+}
+
+#[test]
+fn falls_back_to_tokens_for_renamed_assets() {
+    let release = release("release-v0.2.0-renamed.json");
+    let expected = [
+        (
+            Architecture::Aarch64,
+            Os::Mac,
+            "rari-v0.2.0-arm64-macos.tar.gz",
+        ),
+        (
+            Architecture::Aarch64,
+            Os::Linux,
+            "rari-v0.2.0-arm64-linux-musl.tgz",
+        ),
+        (Architecture::X8664, Os::Mac, "rari-v0.2.0-x64-macos.tar.gz"),
+        (
+            Architecture::X8664,
+            Os::Linux,
+            "rari-v0.2.0-x86_64-linux-musl.tar.gz",
+        ),
+        (
+            Architecture::X8664,
+            Os::Windows,
+            "rari-v0.2.0-x64-windows.zip",
+        ),
+    ];
+    for (arch, platform, name) in expected {
+        let asset = assets::find(release.assets.clone(), arch, platform).unwrap();
+        assert_eq!(asset.name, name);
+    }
+}
+
+#[test]
+fn fails_for_platforms_without_an_asset() {
+    let release = release("release-v0.2.0-renamed.json");
+    let error = assets::find(release.assets, Architecture::Aarch64, Os::Windows).unwrap_err();
+    assert_eq!(
+        error,
+        "unable to find rari-aarch64-pc-windows-msvc.zip in latest release"
+    );
+    let error = assets::find(vec![], Architecture::X86, Os::Linux).unwrap_err();
+    assert_eq!(error, "x86 not supported");
+}
+
+#[test]
+fn unpacks_assets_by_their_extension() {
+    let release = release("release-v0.2.0-renamed.json");
+    for asset in &release.assets {
+        let file_type = assets::file_type(&asset.download_url);
+        let expected = match asset.name.rsplit_once('.').map(|(_, extension)| extension) {
+            Some("zip") => matches!(file_type, DownloadedFileType::Zip),
+            Some("gz" | "tgz") => matches!(file_type, DownloadedFileType::GzipTar),
+            _ => matches!(file_type, DownloadedFileType::Uncompressed),
+        };
+        assert!(expected, "{}", asset.name);
+    }
+}
+
+#[test]
+fn downloads_from_the_latest_release() {
+    let download = assets::download(
+        AssetOverride::default(),
+        || Ok(release("release-v0.1.30.json")),
+        Architecture::Aarch64,
+        Os::Mac,
+    )
+    .unwrap();
+    assert_eq!(download.version_dir, "rari-v0.1.30");
+    assert_eq!(download.version.as_deref(), Some("v0.1.30"));
+    assert!(download.url.ends_with("/rari-aarch64-apple-darwin.tar.gz"));
+}
+
+#[test]
+fn downloads_a_named_asset_override() {
+    let named = |name: &str| AssetOverride {
+        name: Some(name.to_string()),
+        ..AssetOverride::default()
+    };
+    let latest = || Ok(release("release-v0.1.30.json"));
+    let download = assets::download(
+        named("rari-x86_64-unknown-linux-gnu.tar.gz"),
+        latest,
+        Architecture::X8664,
+        Os::Linux,
+    )
+    .unwrap();
+    assert!(download
+        .url
+        .ends_with("/rari-x86_64-unknown-linux-gnu.tar.gz"));
+    let error = assets::download(
+        named("rari-riscv64.tar.gz"),
+        latest,
+        Architecture::X8664,
+        Os::Linux,
+    )
+    .err()
+    .unwrap();
+    assert_eq!(
+        error,
+        "unable to find rari-riscv64.tar.gz in latest release"
+    );
+}
+
+#[test]
+fn downloads_custom_urls_without_asking_github() {
+    let custom = AssetOverride {
+        url: Some("https://example.com/rari-riscv64.tar.gz".to_string()),
+        sha256: Some(recorded_checksum()),
+        ..AssetOverride::default()
+    };
+    let download = assets::download(
+        custom.clone(),
+        || panic!("custom downloads don't look up releases"),
+        Architecture::X8664,
+        Os::Linux,
+    )
+    .unwrap();
+    assert_eq!(download.version_dir, "rari-custom-f4d6bc47ee2e");
+    assert_eq!(download.version, None);
+
+    let unchecked = AssetOverride {
+        sha256: None,
+        ..custom
+    };
+    let error = assets::download(
+        unchecked,
+        || panic!("custom downloads don't look up releases"),
+        Architecture::X8664,
+        Os::Linux,
+    )
+    .err()
+    .unwrap();
+    assert!(error.starts_with("a sha256 is required"), "{error}");
+}
+
+#[test]
+fn reports_failed_release_lookups() {
+    let error = assets::download(
+        AssetOverride::default(),
+        || Err("rate limited".to_string()),
+        Architecture::X8664,
+        Os::Linux,
+    )
+    .err()
+    .unwrap();
+    assert_eq!(error, "rate limited");
+}
+
+#[test]
+fn verifies_the_checksum_of_unpacked_archives() {
+    let work_dir = WorkDir::new("checksum");
+    assert!(work_dir.extract("rari-x86_64-unknown-linux-musl.tar.gz", "rari-v0.1.30"));
+    let checksum = recorded_checksum();
+    assert_eq!(
+        sha256::hex_digest(&fs::read("rari-v0.1.30/rari").unwrap()),
+        checksum
+    );
+    verify_checksum("rari-v0.1.30", "rari-v0.1.30/rari", "fixture", &checksum).unwrap();
+    verify_checksum(
+        "rari-v0.1.30",
+        "rari-v0.1.30/rari",
+        "fixture",
+        &checksum.to_uppercase(),
+    )
+    .unwrap();
+    assert_eq!(work_dir.entries(), ["rari-v0.1.30"]);
+}
+
+#[test]
+fn removes_installs_with_a_checksum_mismatch() {
+    let work_dir = WorkDir::new("mismatch");
+    assert!(work_dir.extract("rari-x86_64-unknown-linux-musl.tar.gz", "rari-v0.1.30"));
+    let error = verify_checksum(
+        "rari-v0.1.30",
+        "rari-v0.1.30/rari",
+        "fixture",
+        &"0".repeat(64),
+    )
+    .unwrap_err();
+    assert!(
+        error.starts_with("checksum mismatch for fixture"),
+        "{error}"
+    );
+    assert!(work_dir.entries().is_empty());
+}
+
+#[test]
+fn finds_no_binary_in_archives_with_folders() {
+    let work_dir = WorkDir::new("nested");
+    assert!(work_dir.extract("rari-nested.tar.gz", "rari-local-000000000000"));
+    // `local_archive::extract` expects the binary at the top.
+    assert!(fs::metadata("rari-local-000000000000/rari").is_err());
+    assert!(fs::metadata("rari-local-000000000000/bin/rari").is_ok());
+}
+
+#[test]
+fn finds_the_managed_install() {
+    let work_dir = WorkDir::new("managed");
+    assert_eq!(managed_install(Os::Linux, None), None);
+    fs::create_dir_all("rari-v0.1.29").unwrap();
+    assert_eq!(managed_install(Os::Linux, None), None);
+    work_dir.install("rari-v0.1.30", "rari");
+    assert_eq!(
+        managed_install(Os::Linux, None).as_deref(),
+        Some("rari-v0.1.30/rari")
+    );
+    assert_eq!(managed_install(Os::Windows, None), None);
+}
+
+#[test]
+fn collects_other_installs() {
+    let work_dir = WorkDir::new("gc");
+    work_dir.install("rari-v0.1.29", "rari");
+    work_dir.install("rari-v0.1.30", "rari");
+    work_dir.install("rari-custom-f4d6bc47ee2e", "rari");
+    fs::write("state.json", "{}").unwrap();
+    remove_other_installs("rari-v0.1.30", None).unwrap();
+    assert_eq!(work_dir.entries(), ["rari-v0.1.30", "state.json"]);
+}
+
+#[test]
+fn leaves_collecting_to_the_instance_holding_the_lock() {
+    let work_dir = WorkDir::new("gc-locked");
+    work_dir.install("rari-v0.1.29", "rari");
+    work_dir.install("rari-v0.1.30", "rari");
+    let other_instance = lock::Lock::acquire("gc", 60).unwrap();
+    remove_other_installs("rari-v0.1.30", None).unwrap();
+    assert_eq!(
+        work_dir.entries(),
+        ["gc.lock", "rari-v0.1.29", "rari-v0.1.30"]
+    );
+    drop(other_instance);
+    remove_other_installs("rari-v0.1.30", None).unwrap();
+    assert_eq!(work_dir.entries(), ["rari-v0.1.30"]);
+}
+
+#[test]
+fn takes_over_stale_locks_only() {
+    let work_dir = WorkDir::new("gc-stale");
+    work_dir.install("rari-v0.1.29", "rari");
+    work_dir.install("rari-v0.1.30", "rari");
+    // Created by another instance that hasn't written its time yet.
+    fs::write("gc.lock", "").unwrap();
+    remove_other_installs("rari-v0.1.30", None).unwrap();
+    assert_eq!(
+        work_dir.entries(),
+        ["gc.lock", "rari-v0.1.29", "rari-v0.1.30"]
+    );
+    // Left empty by an instance that crashed right after creating it.
+    File::options()
+        .write(true)
+        .open("gc.lock")
+        .unwrap()
+        .set_modified(UNIX_EPOCH)
+        .unwrap();
+    remove_other_installs("rari-v0.1.30", None).unwrap();
+    assert_eq!(work_dir.entries(), ["rari-v0.1.30"]);
+    // Left by a crashed instance.
+    work_dir.install("rari-v0.1.29", "rari");
+    fs::write("gc.lock", "0 1f").unwrap();
+    remove_other_installs("rari-v0.1.30", None).unwrap();
+    assert_eq!(work_dir.entries(), ["rari-v0.1.30"]);
+}
@@ -0,0 +1,111 @@
+//! The mdn/interactive-examples checkout next to the content, so edits to an
+//! example and to the page embedding it happen in one project.
+//!
+//! Examples are found through the `meta.json` files of that repository,
+//! which list the source files of each example with its title (for
+//! `{{InteractiveExample}}`) and page file name (for the older
+//! `{{EmbedInteractiveExample}}`).
+
+use zed_extension_api::{process::Command, serde_json, Worktree};
+
+use crate::{content, git, macros, paths, settings::MdnSettings};
+
+/// How rari is told about the checkout.
+pub const ENV: &str = "INTERACTIVE_EXAMPLES_ROOT";
+
+/// Folder name of the checkout next to the content checkout.
+const SIBLING: &str = "interactive-examples";
+
+/// Absolute path of the interactive-examples checkout: the
+/// `interactive_examples_path` setting, or a sibling of the content
+/// checkout.
+pub fn root(worktree: &Worktree, settings: &MdnSettings) -> Option<String> {
+    let root = match &settings.interactive_examples_path {
+        Some(path) if path.starts_with('/') => path.clone(),
+        Some(path) => format!("{}/{path}", worktree.root_path()),
+        None => format!("{}/../{SIBLING}", content::repo_root(worktree)),
+    };
+    let root = paths::normalize(&root, zed_extension_api::current_platform().0);
+    // The extension can't look outside of its work directory itself.
+    Command::new("test")
+        .args(["-d", &format!("{root}/live-examples")])
+        .output()
+        .is_ok_and(|output| output.status == Some(0))
+        .then_some(root)
+}
+
+/// An interactive example embedded in a page.
+pub struct Example {
+    /// Zero based line of the macro call.
+    pub line: usize,
+    /// The title or page file name the example is looked up by.
+    pub key: String,
+    /// Absolute paths of its source files, empty when it wasn't found.
+    pub files: Vec<String>,
+}
+
+/// The interactive examples embedded in `source` with their source files in
+/// the checkout at `root`.
+pub fn examples(root: &str, source: &str) -> Vec<Example> {
+    macros::calls(source)
+        .into_iter()
+        .filter_map(|call| {
+            let arg = call.args?.into_iter().next()?;
+            let arg = arg.trim_matches(|c| c == '"' || c == '\'').to_string();
+            let (field, key) = if call.name.eq_ignore_ascii_case("InteractiveExample") {
+                ("title", arg)
+            } else if call.name.eq_ignore_ascii_case("EmbedInteractiveExample") {
+                // `pages/js/array-at.html`
+                let file_name = arg.rsplit('/').next().unwrap_or(&arg).to_string();
+                ("fileName", file_name)
+            } else {
+                return None;
+            };
+            Some(Example {
+                line: call.line,
+                files: source_files(root, field, &key),
+                key,
+            })
+        })
+        .collect()
+}
+
+fn source_files(root: &str, field: &str, key: &str) -> Vec<String> {
+    let needle = format!("\"{field}\": \"{key}\"");
+    // git grep exits with 1 when nothing matches.
+    let meta_files = git::run(
+        root,
+        &["grep", "-l", "-F", "-e", &needle, "--", "*meta.json"],
+    )
+    .unwrap_or_default();
+    let mut files = vec![];
+    for meta_file in meta_files.lines() {
+        let Ok(output) = Command::new("cat")
+            .arg(format!("{root}/{meta_file}"))
+            .output()
+        else {
+            continue;
+        };
+        let Ok(meta) = serde_json::from_slice::<serde_json::Value>(&output.stdout) else {
+            continue;
+        };
+        let Some(pages) = meta.get("pages").and_then(|pages| pages.as_object()) else {
+            continue;
+        };
+        for page in pages.values() {
+            if page.get(field).and_then(|value| value.as_str()) != Some(key) {
+                continue;
+            }
+            let Some(page) = page.as_object() else {
+                continue;
+            };
+            // `exampleCode`, `cssExampleSrc`, `jsExampleSrc`, …
+            files.extend(
+                page.values()
+                    .filter_map(|value| value.as_str()?.strip_prefix("./live-examples/"))
+                    .map(|path| format!("{root}/live-examples/{path}")),
+            );
+        }
+    }
+    files
+}
@@ -0,0 +1,49 @@
+//! Turns rari's `--issues` JSON report into `file:line:column` entries.
+//!
+//! The report layout has changed between rari releases, so instead of a
+//! fixed schema we collect every object that names a file and a line.
+
+use zed_extension_api::serde_json::Value;
+
+pub struct Issue {
+    pub file: String,
+    pub line: u64,
+    pub column: u64,
+    pub message: String,
+}
+
+pub fn parse(report: &Value) -> Vec<Issue> {
+    let mut issues = vec![];
+    collect(report, &mut issues);
+    issues.sort_by(|a, b| (&a.file, a.line, a.column).cmp(&(&b.file, b.line, b.column)));
+    issues
+}
+
+fn collect(value: &Value, issues: &mut Vec<Issue>) {
+    match value {
+        Value::Array(values) => values.iter().for_each(|value| collect(value, issues)),
+        Value::Object(map) => {
+            let str_field = |keys: &[&str]| {
+                keys.iter()
+                    .find_map(|key| map.get(*key).and_then(Value::as_str))
+            };
+            let num_field = |keys: &[&str]| {
+                keys.iter()
+                    .find_map(|key| map.get(*key).and_then(Value::as_u64))
+            };
+            if let (Some(file), Some(line)) = (str_field(&["file", "path"]), num_field(&["line"])) {
+                issues.push(Issue {
+                    file: file.to_string(),
+                    line,
+                    column: num_field(&["col", "column"]).unwrap_or(0),
+                    message: str_field(&["display", "message", "explanation", "name"])
+                        .unwrap_or("issue")
+                        .to_string(),
+                });
+            } else {
+                map.values().for_each(|value| collect(value, issues));
+            }
+        }
+        _ => {}
+    }
+}
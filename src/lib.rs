@@ -1,18 +1,264 @@
-use std::{fs, iter::once};
+mod anchors;
+mod assets;
+mod baseline;
+mod ci;
+mod command_line;
+mod compat;
+mod container;
+mod content;
+mod crash_loop;
+mod dictionary;
+mod disk;
+mod example_repos;
+mod export;
+mod flaws;
+mod freshness;
+mod front_matter;
+mod git;
+mod glob;
+mod glossary;
+mod homebrew;
+mod index;
+mod initialization;
+mod install_dir;
+#[cfg(all(test, feature = "installer-tests"))]
+mod installer_tests;
+mod interactive_examples;
+mod issues;
+mod lint;
+mod local_archive;
+mod lock;
+mod macro_replace;
+mod macro_source;
+mod macros;
+mod markdown;
+mod markup;
+mod metadata;
+mod metrics;
+mod migrate;
+mod onboarding;
+mod page_types;
+mod patch;
+mod paths;
+mod presets;
+mod preview;
+mod processes;
+mod profile;
+mod rari;
+mod redirects;
+mod references;
+mod release_notes;
+mod remote;
+mod render_diff;
+mod scaffold;
+mod schemas;
+mod see_also;
+mod settings;
+mod sha256;
+mod shell_env;
+mod shim;
+mod short_title;
+mod sidebar;
+mod slash_commands;
+mod source_build;
+mod state;
+mod stats;
+mod status;
+mod table;
+mod titles;
+mod toolchain;
+mod translation_memory;
+mod version_manager;
+mod xref;
 
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    hash::{DefaultHasher, Hash, Hasher},
+    iter::once,
+    time::Instant,
+};
+
+use index::ContentIndex;
+use macros::Registry;
+use settings::MdnSettings;
+use state::State;
+use status::BinarySource;
 use zed_extension_api::{
-    self as zed, settings::LspSettings, Architecture, Command, LanguageServerId, Os, Result,
-    Worktree,
+    self as zed, lsp::Completion, serde_json, settings::LspSettings, CodeLabel, CodeLabelSpan,
+    Command, LanguageServerId, Result, SlashCommand, SlashCommandArgumentCompletion,
+    SlashCommandOutput, Worktree,
 };
 
 pub struct RariBinary {
     path: String,
+    source: BinarySource,
     args: Option<Vec<String>>,
     environment: Option<Vec<(String, String)>>,
 }
 
 pub struct MDN {
     binary_path: Option<String>,
+    /// Hash of the [`LspSettings`] `binary_path` was resolved with.
+    settings_hash: Option<u64>,
+    /// Worktrees that went through the [`onboarding`] checks.
+    checked_worktrees: HashSet<u64>,
+    /// Macro registry of the most recently started worktree, used for
+    /// completion labels which don't know their worktree.
+    registry: Option<Registry>,
+    /// Index of the most recently started worktree, for slash command
+    /// argument completion which doesn't know its worktree either.
+    index: Option<ContentIndex>,
+    /// Translation memory file of the most recently started worktree, for
+    /// slash command argument completion, see [`translation_memory`].
+    translation_memory: Option<String>,
+    /// Summary of the active rari per worktree, see [`status::describe`].
+    statuses: HashMap<u64, String>,
+    /// Problems with the checkout per worktree, see [`onboarding`].
+    checkout_problems: HashMap<u64, Vec<String>>,
+    /// Stale checkout warnings per worktree, see [`freshness`].
+    stale_warnings: HashMap<u64, String>,
+    /// The checkout per worktree when rari was last started, see
+    /// [`git::checked_out`].
+    heads: HashMap<u64, String>,
+    /// Problems with the settings per worktree, see
+    /// [`MdnSettings::problems`].
+    settings_problems: HashMap<u64, Vec<String>>,
+    /// Worktrees where no rari could be found or installed, see
+    /// [`MDN::rari_unavailable`].
+    unavailable: HashSet<u64>,
+    /// Recent server starts per worktree, see [`crash_loop`].
+    launches: HashMap<u64, crash_loop::Launches>,
+    /// The last language server command per worktree as a terminal line, see
+    /// [`command_line`].
+    commands: HashMap<u64, String>,
+    /// Whether binaries found on the system are recent enough, by path, see
+    /// [`status::meets_minimum`].
+    checked_binaries: HashMap<String, bool>,
+}
+
+fn latest_release() -> Result<zed::GithubRelease> {
+    zed::latest_github_release(
+        "mdn/rari",
+        zed::GithubReleaseOptions {
+            require_assets: true,
+            pre_release: false,
+        },
+    )
+}
+
+fn is_file(path: &str) -> bool {
+    fs::metadata(path).is_ok_and(|stat| stat.is_file())
+        // Outside of the work directory, see `install_dir`.
+        || path.starts_with('/') && install_dir::is_file(path)
+}
+
+/// A managed install left by a previous session. With an `install_dir`,
+/// installs from before it was set are moved there.
+fn managed_install(platform: zed::Os, install_dir: Option<&str>) -> Option<String> {
+    let binary = match platform {
+        zed::Os::Mac | zed::Os::Linux => "rari",
+        zed::Os::Windows => "rari.exe",
+    };
+    let local = fs::read_dir(".")
+        .ok()?
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+        .filter(|name| name.starts_with("rari-"))
+        .find(|dir| is_file(&format!("{dir}/{binary}")));
+    match (install_dir, local) {
+        (None, local) => local.map(|dir| format!("{dir}/{binary}")),
+        (Some(install_dir), Some(dir)) => install_dir::relocate(&dir, install_dir)
+            .inspect_err(|e| println!("mdn: {e}"))
+            .ok()
+            .map(|()| format!("{install_dir}/{dir}/{binary}")),
+        (Some(install_dir), None) => install_dir::find(install_dir, binary),
+    }
+}
+
+/// Removes installs other than `keep`, in the work directory and in
+/// `install_dir`. Another Zed instance may be collecting at the same time, in
+/// which case it is left to that one.
+fn remove_other_installs(keep: &str, install_dir: Option<&str>) -> Result<()> {
+    let Some(_lock) = lock::Lock::acquire("gc", GC_LOCK_STALE_SECS) else {
+        return Ok(());
+    };
+    if let Some(install_dir) = install_dir {
+        install_dir::remove_other_installs(install_dir, keep);
+    }
+    let entries = fs::read_dir(".").map_err(|e| format!("failed to list working directory {e}"))?;
+    for entry in entries {
+        let entry = entry.map_err(|e| format!("failed to load directory entry {e}"))?;
+        if entry.file_name().to_str() != Some(keep) {
+            fs::remove_dir_all(entry.path()).ok();
+        }
+    }
+    Ok(())
+}
+
+/// Fails and removes `version_dir` unless the rari at `local_path` has the
+/// SHA-256 `expected`.
+fn verify_checksum(
+    version_dir: &str,
+    local_path: &str,
+    origin: &str,
+    expected: &str,
+) -> Result<()> {
+    let actual = fs::read(local_path)
+        .map(|binary| sha256::hex_digest(&binary))
+        .map_err(|e| format!("failed to read {local_path}: {e}"))?;
+    if !actual.eq_ignore_ascii_case(expected) {
+        fs::remove_dir_all(version_dir).ok();
+        return Err(format!(
+            "checksum mismatch for {origin}: expected {expected}, got {actual}"
+        ));
+    }
+    Ok(())
+}
+
+/// The steps after a new rari was unpacked to `local_path` in the work
+/// directory, shared by downloads and local archives. `origin` is where it
+/// came from, for errors.
+fn finish_install(
+    version_dir: &str,
+    local_path: &str,
+    origin: &str,
+    checksum: Option<&str>,
+    settings: &MdnSettings,
+    install_dir: Option<&str>,
+) -> Result<()> {
+    if let Some(expected) = checksum {
+        verify_checksum(version_dir, local_path, origin, expected)?;
+    }
+
+    zed::make_file_executable(local_path)?;
+    if settings.strip_binary {
+        disk::strip(local_path);
+    }
+    // After stripping, which must not have broken it either.
+    if status::version(local_path, BinarySource::Path).is_none() {
+        fs::remove_dir_all(version_dir).ok();
+        return Err(format!(
+            "the rari from {origin} doesn't run, is it built for this platform?"
+        ));
+    }
+    if let Some(install_dir) = install_dir {
+        install_dir::relocate(version_dir, install_dir)?;
+    }
+    Ok(())
+}
+
+const GC_LOCK_STALE_SECS: u64 = 60;
+/// The lock is refreshed between the steps of an install, but a download is
+/// a single call, which for a large release over a slow connection takes a
+/// while.
+const INSTALL_LOCK_STALE_SECS: u64 = 10 * 60;
+
+fn settings_hash(lsp_settings: Option<&LspSettings>) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    lsp_settings
+        .and_then(|lsp_settings| serde_json::to_string(lsp_settings).ok())
+        .hash(&mut hasher);
+    hasher.finish()
 }
 
 impl MDN {
@@ -21,20 +267,47 @@ impl MDN {
         language_server_id: &LanguageServerId,
         worktree: &zed::Worktree,
     ) -> Result<RariBinary> {
+        let mut settings = MdnSettings::for_worktree(worktree);
+        // Ahead of everything else, even the shell environment, for quick
+        // extension rebuild and reload loops.
+        if let Some(path) = settings
+            .developer_rari
+            .clone()
+            .filter(|_| settings.developer_mode)
+        {
+            return Ok(RariBinary {
+                path,
+                source: BinarySource::Developer,
+                args: None,
+                environment: None,
+            });
+        }
+
         let mut args: Option<Vec<String>> = None;
 
         let (platform, arch) = zed::current_platform();
         let environment = match platform {
-            zed::Os::Mac | zed::Os::Linux => Some(worktree.shell_env()),
+            zed::Os::Mac | zed::Os::Linux => Some(shell_env::get(worktree)),
             zed::Os::Windows => None,
         };
 
-        if let Ok(lsp_settings) = LspSettings::for_worktree("mdn-lsp", worktree) {
+        let lsp_settings = settings::lsp_settings(worktree).map(|(_, lsp_settings)| lsp_settings);
+
+        // Settings changed since the last resolution, e.g. before a language
+        // server restart: resolve from scratch.
+        let settings_hash = settings_hash(lsp_settings.as_ref());
+        if self.settings_hash != Some(settings_hash) {
+            self.settings_hash = Some(settings_hash);
+            self.remember_binary_path(None);
+        }
+
+        if let Some(lsp_settings) = lsp_settings {
             if let Some(binary) = lsp_settings.binary {
                 args = binary.arguments;
                 if let Some(path) = binary.path {
                     return Ok(RariBinary {
                         path: path.clone(),
+                        source: BinarySource::Settings,
                         args,
                         environment,
                     });
@@ -42,89 +315,405 @@ impl MDN {
             }
         }
 
-        if let Some(path) = worktree.which("rari") {
+        if let Some(checkout) = &settings.rari_source_path {
+            let path = source_build::build(
+                language_server_id,
+                checkout,
+                environment.as_deref().unwrap_or_default(),
+            )?;
+            return Ok(RariBinary {
+                path,
+                source: BinarySource::Source,
+                args,
+                environment,
+            });
+        }
+
+        // Same variables as the rari npm package, so one environment
+        // configures both the CLI and the editor.
+        let env_var = |name: &str| {
+            environment.as_ref().and_then(|env| {
+                env.iter()
+                    .find(|(key, value)| key == name && !value.is_empty())
+                    .map(|(_, value)| value.clone())
+            })
+        };
+        if let Some(path) = env_var("RARI_BINARY_PATH") {
             return Ok(RariBinary {
                 path,
+                source: BinarySource::Environment,
                 args,
                 environment,
             });
         }
 
-        if let Some(path) = &self.binary_path {
-            if fs::metadata(path).is_ok_and(|stat| stat.is_file()) {
+        // The version mdn/content pins through the @mdn/rari package.
+        // Not read through the worktree, which fails on a link to a binary.
+        let node_modules = format!("{}/node_modules/.bin/rari", worktree.root_path());
+        let installed = match platform {
+            zed::Os::Mac | zed::Os::Linux => install_dir::is_file(&node_modules),
+            zed::Os::Windows => shim::cmd_exists(&format!("{node_modules}.cmd")),
+        };
+        if let Some(path) = installed
+            .then(|| shim::resolve(&node_modules, platform))
+            .flatten()
+        {
+            return Ok(RariBinary {
+                path,
+                source: BinarySource::NodeModules,
+                args,
+                environment,
+            });
+        }
+
+        // Ahead of PATH, where mise and asdf only put a shim.
+        if let Some(path) =
+            version_manager::resolve(worktree, environment.as_deref().unwrap_or_default())
+        {
+            return Ok(RariBinary {
+                path,
+                source: BinarySource::VersionManager,
+                args,
+                environment,
+            });
+        }
+
+        // npm also puts `rari.cmd` on the PATH, which Windows can't spawn.
+        let on_path = match platform {
+            zed::Os::Mac | zed::Os::Linux => None,
+            zed::Os::Windows => worktree.which("rari.exe"),
+        };
+        if let Some(path) = on_path
+            .or_else(|| worktree.which("rari"))
+            .and_then(|path| shim::resolve(&path, platform))
+            .filter(|path| {
+                status::meets_minimum(path, BinarySource::Path, &mut self.checked_binaries)
+            })
+        {
+            return Ok(RariBinary {
+                path,
+                source: BinarySource::Path,
+                args,
+                environment,
+            });
+        }
+
+        // Zed launched from the Dock doesn't have Homebrew on its PATH.
+        if platform == zed::Os::Mac {
+            if let Some(path) = homebrew::resolve(&mut self.checked_binaries) {
+                return Ok(RariBinary {
+                    path,
+                    source: BinarySource::Homebrew,
+                    args,
+                    environment,
+                });
+            }
+        }
+
+        let install_dir =
+            install_dir::resolve(&settings, environment.as_deref().unwrap_or_default());
+        // Air-gapped machines, never goes to the network.
+        if let Some(archive) = &settings.local_archive {
+            let binary = match platform {
+                zed::Os::Mac | zed::Os::Linux => "rari",
+                zed::Os::Windows => "rari.exe",
+            };
+            let (archive, version_dir) =
+                local_archive::version_dir(archive, environment.as_deref().unwrap_or_default())?;
+            let local_path = format!("{version_dir}/{binary}");
+            let binary_path = match &install_dir {
+                Some(install_dir) => format!("{install_dir}/{local_path}"),
+                None => local_path.clone(),
+            };
+            if !is_file(&binary_path) {
+                // Like downloads, see below.
+                let install_lock = lock::Lock::acquire("install", INSTALL_LOCK_STALE_SECS).ok_or(
+                    "another Zed instance is installing rari, it is used once that is done",
+                )?;
+                let checksum = settings
+                    .assets
+                    .get(&assets::platform_key(arch, platform))
+                    .and_then(|asset| asset.sha256.clone());
+                local_archive::extract(&archive, &version_dir, binary)?;
+                install_lock.refresh();
+                finish_install(
+                    &version_dir,
+                    &local_path,
+                    &archive,
+                    checksum.as_deref(),
+                    &settings,
+                    install_dir.as_deref(),
+                )?;
+                install_lock.refresh();
+                metrics::record(worktree, |metrics| metrics.installs += 1);
+                remove_other_installs(&version_dir, install_dir.as_deref())?;
+            }
+            self.remember_binary_path(Some(&binary_path));
+            return Ok(RariBinary {
+                path: binary_path,
+                source: BinarySource::Managed,
+                args,
+                environment,
+            });
+        }
+
+        let mut state = State::load();
+        let update_check_due = state.update_check_due(settings.update_check_interval_hours);
+        // Another Zed instance may have removed the cached install since.
+        if !self.binary_path.as_ref().is_some_and(|path| is_file(path)) {
+            self.remember_binary_path(None);
+        }
+        let installed = self
+            .binary_path
+            .clone()
+            .or_else(|| managed_install(platform, install_dir.as_deref()))
+            .filter(|path| is_file(path));
+        if let Some(path) = &installed {
+            if update_check_due && !settings.auto_update {
+                // Only note that there is something newer, see
+                // `status::describe`.
+                match latest_release() {
+                    Ok(release) => state.record_update_check(&release.version),
+                    Err(_) => state.defer_update_check(),
+                }
+            }
+            if !update_check_due || !settings.auto_update {
+                self.remember_binary_path(Some(path));
                 return Ok(RariBinary {
                     path: path.clone(),
+                    source: BinarySource::Managed,
                     args,
                     environment,
                 });
             }
         }
 
-        let release = zed::latest_github_release(
-            "mdn/rari",
-            zed::GithubReleaseOptions {
-                require_assets: true,
-                pre_release: false,
+        let skip_download =
+            env_var("RARI_SKIP_DOWNLOAD").is_some_and(|value| value != "0" && value != "false");
+        let binary_path = match (skip_download, installed) {
+            (true, Some(path)) => path,
+            (true, None) => {
+                return Err(
+                    "rari not found and RARI_SKIP_DOWNLOAD is set, set RARI_BINARY_PATH or install rari"
+                        .to_string(),
+                )
+            }
+            (false, installed) => match self.install_latest(
+                language_server_id,
+                worktree,
+                &mut settings,
+                &mut state,
+                (arch, platform),
+                install_dir.as_deref(),
+            ) {
+                Ok(path) => path,
+                // Offline or rate limited, the install at hand still works.
+                Err(e) => {
+                    let Some(path) = installed else {
+                        return Err(e);
+                    };
+                    println!("mdn: updating rari failed, using the installed one: {e}");
+                    state.defer_update_check();
+                    path
+                }
             },
-        )?;
-
-        let assert_name = match (arch, platform) {
-            (Architecture::Aarch64, Os::Mac) => "rari-aarch64-apple-darwin.tar.gz",
-            (Architecture::Aarch64, Os::Linux) => "rari-aarch64-unknown-linux-musl.tar.gz",
-            (Architecture::Aarch64, Os::Windows) => "rari-aarch64-pc-windows-msvc.zip",
-            (Architecture::X86, _) => return Err("x86 not supported".to_string()),
-            (Architecture::X8664, Os::Mac) => "rari-x86_64-apple-darwin.tar.gz",
-            (Architecture::X8664, Os::Linux) => "rari-x86_64-pc-windows-msvc.zip",
-            (Architecture::X8664, Os::Windows) => "rari-x86_64-unknown-linux-musl.tar.gz",
         };
 
-        let download_url = release
+        self.remember_binary_path(Some(&binary_path));
+        Ok(RariBinary {
+            path: binary_path,
+            source: BinarySource::Managed,
+            args,
+            environment,
+        })
+    }
+}
+
+impl MDN {
+    /// Downloads and installs the latest rari, or the configured asset, and
+    /// returns its path.
+    fn install_latest(
+        &mut self,
+        language_server_id: &LanguageServerId,
+        worktree: &Worktree,
+        settings: &mut MdnSettings,
+        state: &mut State,
+        (arch, platform): (zed::Architecture, zed::Os),
+        install_dir: Option<&str>,
+    ) -> Result<String> {
+        let custom_asset = settings
             .assets
-            .into_iter()
-            .find(|asset| asset.name == assert_name)
-            .ok_or(format!("unable to find {assert_name} in latest release"))?
-            .download_url;
+            .remove(&assets::platform_key(arch, platform))
+            .unwrap_or_default();
+        let checksum = custom_asset.sha256.clone();
+        let assets::Download {
+            url: download_url,
+            version_dir,
+            version: release_version,
+        } = assets::download(
+            custom_asset,
+            || {
+                let release = latest_release()?;
+                state.record_update_check(&release.version);
+                Ok(release)
+            },
+            arch,
+            platform,
+        )?;
 
-        let version_dir = format!("rari-{}", release.version);
-        let binary_path = match platform {
+        // Downloads always go to the work directory first, the only place
+        // extensions can write to.
+        let local_path = match platform {
             zed::Os::Mac | zed::Os::Linux => format!("{version_dir}/rari"),
             zed::Os::Windows => format!("{version_dir}/rari.exe"),
         };
+        let binary_path = match &install_dir {
+            Some(install_dir) => format!("{install_dir}/{local_path}"),
+            None => local_path.clone(),
+        };
 
-        if !fs::metadata(&binary_path).is_ok_and(|stat| stat.is_file()) {
+        // One instance installs. Others fail right away instead of blocking
+        // the extension, and find the binary in place on Zed's next attempt.
+        let install_lock = if is_file(&binary_path) {
+            None
+        } else {
+            Some(
+                lock::Lock::acquire("install", INSTALL_LOCK_STALE_SECS).ok_or(
+                    "another Zed instance is installing rari, it is used once that is done",
+                )?,
+            )
+        };
+        if !is_file(&binary_path) {
             zed::set_language_server_installation_status(
                 language_server_id,
                 &zed::LanguageServerInstallationStatus::Downloading,
             );
 
-            zed::download_file(
-                &download_url,
+            let previous_install = managed_install(platform, install_dir);
+            disk::ensure_space_for(&download_url)?;
+            let file_type = assets::file_type(&download_url);
+            // Archives are extracted into the version directory, single
+            // files are written to the binary path.
+            let download_path = match file_type {
+                zed::DownloadedFileType::GzipTar | zed::DownloadedFileType::Zip => &version_dir,
+                zed::DownloadedFileType::Gzip | zed::DownloadedFileType::Uncompressed => {
+                    fs::create_dir_all(&version_dir)
+                        .map_err(|e| format!("failed to create {version_dir}: {e}"))?;
+                    &local_path
+                }
+            };
+            zed::download_file(&download_url, download_path, file_type).map_err(|e| {
+                // Don't leave a half extracted version behind.
+                fs::remove_dir_all(&version_dir).ok();
+                format!("failed to download file: {e}")
+            })?;
+            install_lock.iter().for_each(lock::Lock::refresh);
+
+            finish_install(
                 &version_dir,
-                match platform {
-                    zed::Os::Mac | zed::Os::Linux => zed::DownloadedFileType::GzipTar,
-                    zed::Os::Windows => zed::DownloadedFileType::Zip,
-                },
-            )
-            .map_err(|e| format!("failed to download file: {e}"))?;
+                &local_path,
+                &download_url,
+                checksum.as_deref(),
+                settings,
+                install_dir,
+            )?;
+            install_lock.iter().for_each(lock::Lock::refresh);
+            metrics::record(worktree, |metrics| metrics.installs += 1);
+            if let (Some(_), Some(version)) = (previous_install, &release_version) {
+                release_notes::record_upgrade(state, version);
+            }
 
-            zed::make_file_executable(&binary_path)?;
+            remove_other_installs(&version_dir, install_dir)?;
+        }
+        Ok(binary_path)
+    }
 
-            let entries =
-                fs::read_dir(".").map_err(|e| format!("failed to list working directory {e}"))?;
-            for entry in entries {
-                let entry = entry.map_err(|e| format!("failed to load directory entry {e}"))?;
-                if entry.file_name().to_str() != Some(&version_dir) {
-                    fs::remove_dir_all(entry.path()).ok();
-                }
-            }
+    /// Caches the managed rari in memory and in [`State`], where a reloaded
+    /// extension picks it up again.
+    fn remember_binary_path(&mut self, path: Option<&str>) {
+        if self.binary_path.as_deref() == path {
+            return;
         }
+        self.binary_path = path.map(String::from);
+        let mut state = State::load();
+        state.binary_path = self.binary_path.clone();
+        state.settings_hash = self.settings_hash;
+        state.save();
+    }
 
-        self.binary_path = Some(binary_path.clone());
-        Ok(RariBinary {
-            path: binary_path,
-            args,
-            environment,
-        })
+    /// Keeps `command` for `/mdn-command` and logs it with `log_command`.
+    fn record_command(&mut self, worktree: &Worktree, settings: &MdnSettings, command: &Command) {
+        let line = command_line::render(command, &worktree.root_path(), zed::current_platform().0);
+        if settings.log_command {
+            println!("mdn: starting the language server with\n{line}");
+        }
+        self.commands.insert(worktree.id(), line);
+    }
+
+    /// The command rari was last started with in `worktree`, see
+    /// [`command_line`].
+    pub fn command_line(&self, worktree: &Worktree) -> Option<&str> {
+        self.commands.get(&worktree.id()).map(String::as_str)
+    }
+
+    /// The rari binary for one-off runs from slash commands. Unlike
+    /// [`MDN::rari_binary`] this never downloads anything.
+    pub fn installed_rari(&self, worktree: &Worktree) -> Result<String> {
+        settings::lsp_settings(worktree)
+            .and_then(|(_, lsp_settings)| lsp_settings.binary)
+            .and_then(|binary| binary.path)
+            .or_else(|| version_manager::resolve(worktree, &shell_env::get(worktree)))
+            .or_else(|| {
+                let path = worktree.which("rari")?;
+                shim::resolve(&path, zed::current_platform().0)
+            })
+            .or_else(|| self.binary_path.clone().filter(|path| is_file(path)))
+            .ok_or_else(|| "rari is not installed yet, open an MDN page first".to_string())
+    }
+
+    /// Whether starting rari failed for `worktree`, e.g. on an air-gapped
+    /// machine. The client-side checks then cover more ground.
+    pub fn rari_unavailable(&self, worktree: &Worktree) -> bool {
+        self.unavailable.contains(&worktree.id())
+    }
+
+    /// Summary of the rari serving `worktree`, if the server was started,
+    /// followed by settings problems, an incomplete checkout, a branch switch
+    /// since and a stale checkout warning.
+    pub fn status(&self, worktree: &Worktree) -> Option<String> {
+        let mut status = self.statuses.get(&worktree.id())?.clone();
+        // rari keeps the pages it read in memory, a branch switch changing
+        // thousands of files leaves it with the previous branch.
+        let switched = self
+            .heads
+            .get(&worktree.id())
+            .zip(git::checked_out(worktree))
+            .filter(|(started, now)| *started != now)
+            .map(|(started, now)| {
+                format!(
+                    "{now} was checked out after rari started on {started}, \
+                     restart it (`editor: restart language server`) so diagnostics match"
+                )
+            });
+        let incomplete = self
+            .checkout_problems
+            .get(&worktree.id())
+            .into_iter()
+            .flatten()
+            .map(|problem| format!("checkout: {problem}"));
+        let warnings = self
+            .settings_problems
+            .get(&worktree.id())
+            .into_iter()
+            .flatten()
+            .cloned()
+            .chain(incomplete)
+            .chain(switched)
+            .chain(self.stale_warnings.get(&worktree.id()).cloned());
+        for warning in warnings {
+            status.push_str(&format!("\nwarning: {warning}"));
+        }
+        Some(status)
     }
 }
 
@@ -134,27 +723,224 @@ impl zed::Extension for MDN {
         language_server_id: &LanguageServerId,
         worktree: &Worktree,
     ) -> Result<Command> {
-        let rari_binary = self.rari_binary(language_server_id, worktree)?;
+        let start = Instant::now();
+        let settings = MdnSettings::for_worktree(worktree);
+        let problems = MdnSettings::problems(worktree);
+        for problem in &problems {
+            println!("mdn: {problem}");
+        }
+        self.settings_problems.insert(worktree.id(), problems);
+        if !settings.enable {
+            // Zed has no way to decline starting a server other than an
+            // error, so at least make it say what is going on.
+            return Err(
+                "MDN content LSP is disabled for this worktree (mdn-lsp.settings.enable)"
+                    .to_string(),
+            );
+        }
+        self.registry = Some(Registry::for_worktree(worktree, &settings));
+        let head = git::checked_out(worktree);
+        if self.checked_worktrees.contains(&worktree.id())
+            && head.is_some()
+            && self.heads.get(&worktree.id()) != head.as_ref()
+        {
+            // Restarted after a branch switch.
+            println!("mdn: checkout changed, rebuilding the content index");
+            self.index = Some(ContentIndex::build(worktree));
+        }
+        if let Some(head) = head {
+            self.heads.insert(worktree.id(), head);
+        }
+        if !self.checked_worktrees.contains(&worktree.id()) {
+            let problems = onboarding::check(worktree);
+            for problem in &problems {
+                println!("mdn: checkout: {problem}");
+            }
+            self.checkout_problems.insert(worktree.id(), problems);
+            if let (None, Some(file)) = (&settings.container, container::detect(worktree)) {
+                println!(
+                    "mdn: found {file}, set `container` in the settings to run rari in the container"
+                );
+            }
+            self.index = Some(ContentIndex::build(worktree));
+            metadata::refresh(worktree)
+                .inspect_err(|e| println!("mdn: {e}"))
+                .ok();
+            if let Some(warning) = freshness::check(worktree, &settings) {
+                println!("mdn: {warning}");
+                self.stale_warnings.insert(worktree.id(), warning);
+            }
+            self.checked_worktrees.insert(worktree.id());
+        }
+        if settings.translation_memory.is_some() {
+            self.translation_memory = translation_memory::file(worktree).ok();
+            // A dump finished since the last start, before checking whether
+            // the memory is current.
+            if let Some(file) = &self.translation_memory {
+                translation_memory::finish(file);
+            }
+            // Also after a branch switch.
+            translation_memory::refresh(worktree, &settings)
+                .inspect_err(|e| println!("mdn: {e}"))
+                .ok();
+        }
+        if let Some(container) = &settings.container {
+            let command = container::command(worktree, container)?;
+            println!("mdn: running rari in container {}", container.name);
+            self.statuses.insert(
+                worktree.id(),
+                format!("rari in container {}", container.name),
+            );
+            self.record_command(worktree, &settings, &command);
+            return Ok(command);
+        }
+        if let Some(address) = &settings.server_address {
+            let command = remote::bridge(worktree, address)?;
+            println!("mdn: connecting to rari at {address}");
+            self.statuses
+                .insert(worktree.id(), format!("rari at {address} over TCP"));
+            self.record_command(worktree, &settings, &command);
+            return Ok(command);
+        }
+        content::check_content_root(worktree)?;
+        let rari_binary = match self.rari_binary(language_server_id, worktree) {
+            Ok(rari_binary) => rari_binary,
+            Err(e) => {
+                metrics::record(worktree, |metrics| metrics.record_failure(&e));
+                self.unavailable.insert(worktree.id());
+                self.statuses.insert(
+                    worktree.id(),
+                    format!("rari unavailable ({e}), only client-side checks via /mdn-lint"),
+                );
+                return Err(e);
+            }
+        };
+        self.unavailable.remove(&worktree.id());
+        let mut status = status::describe(&rari_binary.path, rari_binary.source);
+        println!("mdn: using {status} at {}", rari_binary.path);
+        if let Some(version) = release_notes::unread() {
+            println!("mdn: rari was updated to {version}, see /mdn-release-notes");
+            status.push_str(&format!("\nupdated to {version}, see /mdn-release-notes"));
+        }
+        self.statuses.insert(worktree.id(), status);
+        if settings.export_binary_path {
+            export::write(&rari_binary.path);
+        }
+        metrics::record(worktree, |metrics| {
+            metrics.record_startup(start.elapsed().as_millis() as u64, rari_binary.source)
+        });
 
-        Ok(Command {
+        let command = Command {
             command: rari_binary.path,
             args: once("lsp".to_string())
                 .chain(rari_binary.args.unwrap_or_default())
                 .collect(),
-            env: once((
-                "CONTENT_ROOT".to_string(),
-                format!("{}/files", worktree.root_path()),
-            ))
-            .chain(rari_binary.environment.unwrap_or_default())
-            .collect(),
+            env: once(("CONTENT_ROOT".to_string(), content::content_root(worktree)))
+                .chain(
+                    interactive_examples::root(worktree, &settings)
+                        .map(|root| (interactive_examples::ENV.to_string(), root)),
+                )
+                .chain(rari_binary.environment.unwrap_or_default())
+                .collect(),
+        };
+        self.record_command(worktree, &settings, &command);
+        if self.launches.entry(worktree.id()).or_default().record() {
+            if let Some(stderr) = crash_loop::probe(&command) {
+                return Err(format!(
+                    "rari exited right after starting {} times in a row and won't be restarted, \
+                     restart the language server once the cause is fixed:\n{stderr}",
+                    crash_loop::MAX_FAST_RESTARTS,
+                ));
+            }
+        }
+        Ok(command)
+    }
+
+    fn language_server_initialization_options(
+        &mut self,
+        _language_server_id: &LanguageServerId,
+        worktree: &Worktree,
+    ) -> Result<Option<serde_json::Value>> {
+        Ok(initialization::options(worktree))
+    }
+
+    fn language_server_additional_workspace_configuration(
+        &mut self,
+        _language_server_id: &LanguageServerId,
+        target_language_server_id: &LanguageServerId,
+        worktree: &Worktree,
+    ) -> Result<Option<serde_json::Value>> {
+        let target = target_language_server_id.as_ref();
+        Ok(dictionary::spellchecker_configuration(target, worktree)
+            .or_else(|| schemas::configuration(target)))
+    }
+
+    fn label_for_completion(
+        &self,
+        _language_server_id: &LanguageServerId,
+        completion: Completion,
+    ) -> Option<CodeLabel> {
+        let info = self.registry.as_ref()?.get(&completion.label)?;
+        let signature = info.signature();
+        Some(CodeLabel {
+            code: String::new(),
+            spans: vec![
+                CodeLabelSpan::literal(info.name.clone(), Some("function".to_string())),
+                CodeLabelSpan::literal(signature[info.name.len()..].to_string(), None),
+                CodeLabelSpan::literal(
+                    match info.deprecated {
+                        true => format!(" deprecated: {}", info.description),
+                        false => format!(" {}", info.description),
+                    },
+                    Some("comment".to_string()),
+                ),
+            ],
+            filter_range: (0..info.name.len()).into(),
         })
     }
 
+    fn complete_slash_command_argument(
+        &self,
+        command: SlashCommand,
+        args: Vec<String>,
+    ) -> Result<Vec<SlashCommandArgumentCompletion>> {
+        Ok(slash_commands::complete(self, command, args))
+    }
+
+    fn run_slash_command(
+        &self,
+        command: SlashCommand,
+        args: Vec<String>,
+        worktree: Option<&Worktree>,
+    ) -> Result<SlashCommandOutput> {
+        slash_commands::run(self, command, args, worktree)
+    }
+
     fn new() -> Self
     where
         Self: Sized,
     {
-        MDN { binary_path: None }
+        processes::stop_all();
+        // Left by the previous load of the extension, see
+        // `remember_binary_path`.
+        let state = State::load();
+        MDN {
+            binary_path: state.binary_path,
+            settings_hash: state.settings_hash,
+            checked_worktrees: HashSet::new(),
+            registry: None,
+            index: None,
+            translation_memory: None,
+            statuses: HashMap::new(),
+            checkout_problems: HashMap::new(),
+            stale_warnings: HashMap::new(),
+            settings_problems: HashMap::new(),
+            heads: HashMap::new(),
+            unavailable: HashSet::new(),
+            launches: HashMap::new(),
+            commands: HashMap::new(),
+            checked_binaries: HashMap::new(),
+        }
     }
 }
 
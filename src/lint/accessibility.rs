@@ -0,0 +1,185 @@
+//! The accessibility items reviewers check by hand.
+
+use super::{Document, Finding, Rule, Severity};
+use crate::markdown::{self, find_word, prose_lines};
+
+pub const IMAGE_ALT: Rule = Rule {
+    name: "image-alt",
+    default_severity: Severity::Warning,
+    check: image_alt,
+};
+
+pub const TABLE_HEADER: Rule = Rule {
+    name: "table-header",
+    default_severity: Severity::Warning,
+    check: table_header,
+};
+
+pub const LINK_TEXT: Rule = Rule {
+    name: "link-text",
+    default_severity: Severity::Warning,
+    check: link_text,
+};
+
+pub const COLOR_ONLY: Rule = Rule {
+    name: "color-only",
+    default_severity: Severity::Hint,
+    check: color_only,
+};
+
+/// Link texts that say nothing about the target out of context.
+const VAGUE_LINK_TEXTS: &[&str] = &[
+    "click here",
+    "here",
+    "this",
+    "this link",
+    "this page",
+    "link",
+    "more",
+    "read more",
+];
+
+const COLORS: &[&str] = &[
+    "red", "green", "blue", "yellow", "orange", "purple", "pink", "gray", "grey",
+];
+
+/// Words that, after a color, make it the only way to tell something apart:
+/// "the red line", "shown in green".
+const COLOR_CONTEXTS: &[&str] = &[
+    "in {}",
+    "{} text",
+    "{} line",
+    "{} lines",
+    "{} box",
+    "{} boxes",
+    "{} button",
+    "{} area",
+    "{} part",
+    "{} item",
+    "{} items",
+];
+
+fn image_alt(document: &Document, findings: &mut Vec<Finding>) {
+    for link in markdown::links(document.source) {
+        if link.image && link.text.is_empty() {
+            findings.push(Finding {
+                line: link.line,
+                column: link.column,
+                message: "image without alt text".to_string(),
+            });
+        }
+    }
+    let lines: Vec<markdown::ProseLine> = prose_lines(document.source).collect();
+    for (i, prose) in lines.iter().enumerate() {
+        let lowercase = prose.text.to_lowercase();
+        for (column, _) in lowercase.match_indices("<img") {
+            // Tags with many attributes are often spread over lines.
+            let mut tag = lowercase[column..].to_string();
+            let mut previous = prose.line;
+            for next in &lines[i + 1..] {
+                if tag.contains('>') || next.line != previous + 1 {
+                    break;
+                }
+                tag.push(' ');
+                tag.push_str(&next.text.to_lowercase());
+                previous = next.line;
+            }
+            let tag = tag.split('>').next().unwrap_or_default();
+            if !tag.contains("alt=") {
+                findings.push(Finding {
+                    line: prose.line,
+                    column,
+                    message: "<img> without an alt attribute".to_string(),
+                });
+            }
+        }
+    }
+}
+
+fn table_header(document: &Document, findings: &mut Vec<Finding>) {
+    let lines: Vec<markdown::ProseLine> = prose_lines(document.source).collect();
+    for (i, prose) in lines.iter().enumerate() {
+        let lowercase = prose.text.to_lowercase();
+        if let Some(column) = lowercase.find("<table") {
+            let has_header = lines[i..]
+                .iter()
+                .map(|prose| prose.text.to_lowercase())
+                .take_while(|text| !text.contains("</table>"))
+                .any(|text| has_header_cell(&text));
+            if !has_header {
+                findings.push(Finding {
+                    line: prose.line,
+                    column,
+                    message: "table without header cells (<th>)".to_string(),
+                });
+            }
+            continue;
+        }
+        // A GFM table always has a header row, but it can be left empty.
+        let is_delimiter_row = |text: &str| {
+            let text = text.trim();
+            text.starts_with('|')
+                && text.contains('-')
+                && text.chars().all(|c| matches!(c, '|' | '-' | ':' | ' '))
+        };
+        let is_empty_header = prose.text.trim().starts_with('|')
+            && prose.text.split('|').all(|cell| cell.trim().is_empty());
+        if is_empty_header
+            && lines
+                .get(i + 1)
+                .is_some_and(|next| is_delimiter_row(&next.text))
+        {
+            findings.push(Finding {
+                line: prose.line,
+                column: 0,
+                message: "table with an empty header row".to_string(),
+            });
+        }
+    }
+}
+
+/// Whether `text` opens a `<th>`, not a `<thead>`.
+fn has_header_cell(text: &str) -> bool {
+    text.match_indices("<th").any(|(i, _)| {
+        text[i + 3..]
+            .chars()
+            .next()
+            .is_none_or(|c| c == '>' || c.is_whitespace())
+    })
+}
+
+fn link_text(document: &Document, findings: &mut Vec<Finding>) {
+    for link in markdown::links(document.source) {
+        let text = link.text.to_lowercase();
+        if !link.image && VAGUE_LINK_TEXTS.contains(&text.as_str()) {
+            findings.push(Finding {
+                line: link.line,
+                column: link.column,
+                message: format!(
+                    "link text \"{}\" doesn't describe the target, name the linked page",
+                    link.text
+                ),
+            });
+        }
+    }
+}
+
+fn color_only(document: &Document, findings: &mut Vec<Finding>) {
+    for prose in prose_lines(document.source) {
+        for color in COLORS {
+            for context in COLOR_CONTEXTS {
+                let phrase = context.replace("{}", color);
+                for column in find_word(&prose.text, &phrase) {
+                    findings.push(Finding {
+                        line: prose.line,
+                        column,
+                        message: format!(
+                            "\"{phrase}\" relies on color alone, also describe it by position, \
+                             shape or label"
+                        ),
+                    });
+                }
+            }
+        }
+    }
+}
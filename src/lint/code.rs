@@ -0,0 +1,55 @@
+//! Checks of the code examples on a page.
+
+use super::{Document, Finding, Rule, Severity};
+use crate::markdown;
+
+pub const CODE_ATTRIBUTION: Rule = Rule {
+    name: "code-attribution",
+    // Most long examples are written for MDN, only adapted ones need this.
+    default_severity: Severity::Off,
+    check: code_attribution,
+};
+
+/// Code blocks up to this many lines never need an attribution.
+const MAX_UNATTRIBUTED_LINES: usize = 30;
+
+/// Lines around a block searched for an attribution.
+const ATTRIBUTION_DISTANCE: usize = 3;
+
+const ATTRIBUTION_MARKERS: &[&str] = &[
+    "adapted from",
+    "based on",
+    "courtesy of",
+    "source:",
+    "license",
+    "copyright",
+    "©",
+];
+
+fn code_attribution(document: &Document, findings: &mut Vec<Finding>) {
+    let lines: Vec<&str> = document.source.lines().collect();
+    for block in markdown::code_blocks(document.source) {
+        if block.len() <= MAX_UNATTRIBUTED_LINES {
+            continue;
+        }
+        let from = block.start.saturating_sub(ATTRIBUTION_DISTANCE);
+        let to = (block.end + ATTRIBUTION_DISTANCE + 1).min(lines.len());
+        let attributed = lines[from..to].iter().any(|line| {
+            let line = line.to_lowercase();
+            ATTRIBUTION_MARKERS
+                .iter()
+                .any(|marker| line.contains(marker))
+        });
+        if !attributed {
+            findings.push(Finding {
+                line: block.start,
+                column: 0,
+                message: format!(
+                    "code example of {} lines without a source attribution, \
+                     add one if it is adapted from elsewhere",
+                    block.len()
+                ),
+            });
+        }
+    }
+}
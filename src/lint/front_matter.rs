@@ -0,0 +1,187 @@
+//! Checks of the front-matter block.
+
+use super::{Document, Finding, Rule, Severity};
+use crate::{
+    front_matter::{FrontMatter, KEY_ORDER},
+    page_types, schemas, short_title, titles,
+};
+
+pub const KEY_ORDER_RULE: Rule = Rule {
+    name: "front-matter-order",
+    default_severity: Severity::Warning,
+    check: key_order,
+};
+
+fn key_order(document: &Document, findings: &mut Vec<Finding>) {
+    let Some(front_matter) = FrontMatter::parse(document.source) else {
+        return;
+    };
+    if let Some(index) = front_matter.first_misordered() {
+        findings.push(Finding {
+            line: front_matter.line_of(index),
+            column: 0,
+            message: format!(
+                "\"{}\" is out of order, front-matter keys go in the order {}",
+                front_matter.entries[index].key,
+                KEY_ORDER.join(", ")
+            ),
+        });
+    }
+}
+
+pub const SHORT_TITLE: Rule = Rule {
+    name: "short-title",
+    default_severity: Severity::Hint,
+    check: short_title,
+};
+
+fn short_title(document: &Document, findings: &mut Vec<Finding>) {
+    let Some(front_matter) = FrontMatter::parse(document.source) else {
+        return;
+    };
+    if front_matter.get("short-title").is_some() {
+        return;
+    }
+    let Some(index) = front_matter
+        .entries
+        .iter()
+        .position(|entry| entry.key == "title")
+    else {
+        return;
+    };
+    let title = front_matter.value("title").unwrap_or_default();
+    if let Some(suggestion) = short_title::suggest(&title) {
+        findings.push(Finding {
+            line: front_matter.line_of(index),
+            column: 0,
+            message: format!(
+                "the title is longer than {} characters, consider `short-title: {suggestion}`",
+                short_title::MAX_SIDEBAR_LENGTH
+            ),
+        });
+    }
+}
+
+pub const SCHEMA: Rule = Rule {
+    name: "front-matter-schema",
+    default_severity: Severity::Hint,
+    check: schema,
+};
+
+/// Unknown and missing keys according to `schemas/front-matter.schema.json`.
+fn schema(document: &Document, findings: &mut Vec<Finding>) {
+    let Some(front_matter) = FrontMatter::parse(document.source) else {
+        return;
+    };
+    let (keys, required) = schemas::front_matter_keys();
+    for (index, entry) in front_matter.entries.iter().enumerate() {
+        if !keys.contains(&entry.key) {
+            findings.push(Finding {
+                line: front_matter.line_of(index),
+                column: 0,
+                message: format!("unknown front-matter key \"{}\"", entry.key),
+            });
+        }
+    }
+    for key in required {
+        if front_matter.get(&key).is_none() {
+            findings.push(Finding {
+                line: 0,
+                column: 0,
+                message: format!("front-matter is missing \"{key}\""),
+            });
+        }
+    }
+}
+
+pub const TITLE_HEADING: Rule = Rule {
+    name: "title-heading",
+    default_severity: Severity::Warning,
+    check: title_heading,
+};
+
+fn title_heading(document: &Document, findings: &mut Vec<Finding>) {
+    let title = FrontMatter::parse(document.source)
+        .and_then(|front_matter| front_matter.title())
+        .unwrap_or_default();
+    for heading in titles::body_headings(document.source) {
+        let message = if heading.text.trim().eq_ignore_ascii_case(title.trim()) {
+            "this heading repeats the title, which is already rendered as the page heading"
+        } else {
+            "level 1 headings are reserved for the title, use `##` for sections"
+        };
+        findings.push(Finding {
+            line: heading.line,
+            column: 0,
+            message: message.to_string(),
+        });
+    }
+}
+
+pub const TITLE_FORMAT: Rule = Rule {
+    name: "title-format",
+    default_severity: Severity::Hint,
+    check: title_format,
+};
+
+fn title_format(document: &Document, findings: &mut Vec<Finding>) {
+    let Some(front_matter) = FrontMatter::parse(document.source) else {
+        return;
+    };
+    let Some(expected) = titles::misformatted(&front_matter) else {
+        return;
+    };
+    let Some(index) = front_matter
+        .entries
+        .iter()
+        .position(|entry| entry.key == "title")
+    else {
+        return;
+    };
+    let page_type = front_matter.page_type().unwrap_or_default();
+    findings.push(Finding {
+        line: front_matter.line_of(index),
+        column: 0,
+        message: format!("titles of {page_type} pages read like \"{expected}\""),
+    });
+}
+
+pub const PAGE_TYPE_AREA: Rule = Rule {
+    name: "page-type-area",
+    default_severity: Severity::Warning,
+    check: page_type_area,
+};
+
+/// Page types that don't belong where the page is, like a `css-property`
+/// below `Web/API`, see `data/page_type_areas.json`.
+fn page_type_area(document: &Document, findings: &mut Vec<Finding>) {
+    let Some(front_matter) = FrontMatter::parse(document.source) else {
+        return;
+    };
+    let (Some(page_type), Some(slug)) = (front_matter.page_type(), front_matter.value("slug"))
+    else {
+        return;
+    };
+    let Some(area) = page_types::area(&slug) else {
+        return;
+    };
+    if area.page_types.contains(&page_type) {
+        return;
+    }
+    let Some(index) = front_matter
+        .entries
+        .iter()
+        .position(|entry| entry.key == "page-type")
+    else {
+        return;
+    };
+    findings.push(Finding {
+        line: front_matter.line_of(index),
+        column: 0,
+        message: format!(
+            "{page_type} isn't a page type for pages below {}, use one of {}",
+            area.prefix,
+            area.page_types.join(", ")
+        ),
+    });
+}
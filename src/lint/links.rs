@@ -0,0 +1,191 @@
+//! Checks of links against the content index.
+
+use std::collections::HashMap;
+
+use super::{Document, Finding, Rule, Severity};
+use crate::{
+    anchors,
+    content::DocUrl,
+    example_repos::{self, Target},
+    glossary, markdown,
+};
+
+pub const BROKEN_LINK: Rule = Rule {
+    name: "broken-link",
+    // rari reports broken links itself, this is for when it's unavailable.
+    default_severity: Severity::Off,
+    check: broken_link,
+};
+
+pub const GLOSSARY_LINK: Rule = Rule {
+    name: "glossary-link",
+    default_severity: Severity::Hint,
+    check: glossary_link,
+};
+
+pub const FRAGMENT_LINK: Rule = Rule {
+    name: "fragment-link",
+    default_severity: Severity::Warning,
+    check: fragment_link,
+};
+
+pub const LINK_TITLE: Rule = Rule {
+    name: "link-title",
+    default_severity: Severity::Hint,
+    check: link_title,
+};
+
+pub const EXAMPLE_LINK: Rule = Rule {
+    name: "example-link",
+    default_severity: Severity::Warning,
+    check: example_link,
+};
+
+/// Title suffixes of API member pages, like "Window: fetch() method".
+const MEMBER_SUFFIXES: &[&str] = &[
+    " method",
+    " static method",
+    " property",
+    " static property",
+    " event",
+    " constructor",
+];
+
+/// What a code link to a page should read: `fetch()` for "Window: fetch()
+/// method", `<div>` for "<div>: The Content Division element".
+fn api_name(title: &str) -> &str {
+    match title.split_once(": ") {
+        Some((tag, _)) if tag.starts_with('<') => tag,
+        Some((_, member)) => MEMBER_SUFFIXES
+            .iter()
+            .find_map(|suffix| member.strip_suffix(suffix))
+            .unwrap_or(title),
+        None => title,
+    }
+}
+
+fn link_title(document: &Document, findings: &mut Vec<Finding>) {
+    let Some(pages) = document.pages else {
+        return;
+    };
+    let titles: HashMap<String, &str> = pages
+        .iter()
+        .filter(|page| page.path.starts_with("files/en-us/"))
+        .map(|page| (page.slug.to_lowercase(), page.title.as_str()))
+        .collect();
+    for link in markdown::links(document.source) {
+        let Some(url) = DocUrl::parse(&link.url) else {
+            continue;
+        };
+        let Some(title) = titles.get(&url.slug.to_lowercase()) else {
+            continue;
+        };
+        let raw_url = link.text.starts_with("http") || link.text.starts_with('/');
+        let message = if raw_url {
+            format!("use the page title \"{title}\" as link text instead of the URL")
+        } else if let Some(code) = link
+            .text
+            .strip_prefix('`')
+            .and_then(|text| text.strip_suffix('`'))
+        {
+            let name = api_name(title);
+            if url.fragment.is_some() || code == name || name.contains(' ') {
+                continue;
+            }
+            format!("link text `{code}` doesn't match the linked page, use `{name}`")
+        } else {
+            continue;
+        };
+        findings.push(Finding {
+            line: link.line,
+            column: link.column,
+            message,
+        });
+    }
+}
+
+fn fragment_link(document: &Document, findings: &mut Vec<Finding>) {
+    let fragments = anchors::fragments(document.source);
+    for link in markdown::links(document.source) {
+        let Some(fragment) = link.url.strip_prefix('#') else {
+            continue;
+        };
+        if !fragments.iter().any(|known| known == fragment) {
+            findings.push(Finding {
+                line: link.line,
+                column: link.column,
+                message: format!(
+                    "no heading or live sample on this page has the id \"{fragment}\""
+                ),
+            });
+        }
+    }
+}
+
+fn glossary_link(document: &Document, findings: &mut Vec<Finding>) {
+    let Some(index) = document.index else {
+        return;
+    };
+    for suggestion in glossary::suggest(document.source, &glossary::terms(index)) {
+        findings.push(Finding {
+            line: suggestion.line,
+            column: suggestion.column,
+            message: format!(
+                "first use of a glossary term, link it with {}",
+                suggestion.replacement()
+            ),
+        });
+    }
+}
+
+fn broken_link(document: &Document, findings: &mut Vec<Finding>) {
+    let Some(index) = document.index else {
+        return;
+    };
+    for link in markdown::links(document.source) {
+        if !link.url.starts_with('/') {
+            continue;
+        }
+        let Some(url) = DocUrl::parse(&link.url) else {
+            continue;
+        };
+        // Pages of other locales, like en-US ones linked from
+        // translated-content, aren't in this checkout.
+        if document
+            .locale
+            .is_some_and(|locale| !url.locale.eq_ignore_ascii_case(locale))
+        {
+            continue;
+        }
+        if index.files.binary_search(&url.file_path()).is_err() {
+            findings.push(Finding {
+                line: link.line,
+                column: link.column,
+                message: format!("no page for {} in this checkout", url.relative()),
+            });
+        }
+    }
+}
+
+fn example_link(document: &Document, findings: &mut Vec<Finding>) {
+    if document.example_repos.is_empty() {
+        return;
+    }
+    for reference in example_repos::references(document.source) {
+        let message = match example_repos::resolve(document.example_repos, &reference) {
+            None | Some(Target::File(_)) => continue,
+            Some(Target::Missing(file)) => {
+                format!("{file} does not exist in the {} checkout", reference.repo)
+            }
+            Some(Target::NoCheckout(checkout)) => format!(
+                "can't check this link, the {} checkout at {checkout} is missing",
+                reference.repo
+            ),
+        };
+        findings.push(Finding {
+            line: reference.line,
+            column: reference.column,
+            message,
+        });
+    }
+}
@@ -0,0 +1,105 @@
+//! Checks of macro calls against the macro registry.
+
+use super::{Document, Finding, Rule, Severity};
+use crate::macros::{calls, delimiter_errors};
+
+pub const UNKNOWN_MACRO: Rule = Rule {
+    name: "unknown-macro",
+    // The bundled registry only covers the common macros.
+    default_severity: Severity::Off,
+    check: unknown_macro,
+};
+
+pub const MACRO_ARGUMENTS: Rule = Rule {
+    name: "macro-arguments",
+    default_severity: Severity::Warning,
+    check: macro_arguments,
+};
+
+fn macro_arguments(document: &Document, findings: &mut Vec<Finding>) {
+    for call in calls(document.source) {
+        let Some(info) = document.registry.get(&call.name) else {
+            continue;
+        };
+        let args = call.args.unwrap_or_default();
+        let required = info.params.iter().filter(|param| !param.optional).count();
+        let mut problem = |message: String| {
+            findings.push(Finding {
+                line: call.line,
+                column: call.column,
+                message: format!("{message}, expected {}", info.signature()),
+            })
+        };
+        if args.len() < required || args.len() > info.params.len() {
+            problem(format!("{} takes {} arguments", info.name, args.len()));
+            continue;
+        }
+        for (arg, param) in args.iter().zip(&info.params) {
+            if !param.param_type.accepts(arg) {
+                problem(format!(
+                    "{arg} is not a {} for {}",
+                    param.param_type.as_str(),
+                    param.name
+                ));
+            }
+        }
+    }
+}
+
+pub const DEPRECATED_MACRO: Rule = Rule {
+    name: "deprecated-macro",
+    default_severity: Severity::Warning,
+    check: deprecated_macro,
+};
+
+fn deprecated_macro(document: &Document, findings: &mut Vec<Finding>) {
+    for call in calls(document.source) {
+        let Some(info) = document.registry.get(&call.name) else {
+            continue;
+        };
+        if !info.deprecated {
+            continue;
+        }
+        let message = match info.rewrite(&call.args.unwrap_or_default()) {
+            Some(replacement) if replacement.is_empty() => {
+                format!("{} is deprecated, remove it", info.name)
+            }
+            Some(replacement) => format!("{} is deprecated, use {replacement}", info.name),
+            None => format!("{} is deprecated", info.name),
+        };
+        findings.push(Finding {
+            line: call.line,
+            column: call.column,
+            message,
+        });
+    }
+}
+
+fn unknown_macro(document: &Document, findings: &mut Vec<Finding>) {
+    for call in calls(document.source) {
+        if document.registry.get(&call.name).is_none() {
+            findings.push(Finding {
+                line: call.line,
+                column: call.column,
+                message: format!("unknown macro \"{}\"", call.name),
+            });
+        }
+    }
+}
+
+pub const MACRO_DELIMITERS: Rule = Rule {
+    name: "macro-delimiters",
+    // rari fails to render everything after the typo.
+    default_severity: Severity::Error,
+    check: macro_delimiters,
+};
+
+fn macro_delimiters(document: &Document, findings: &mut Vec<Finding>) {
+    for error in delimiter_errors(document.source) {
+        findings.push(Finding {
+            line: error.line,
+            column: error.column,
+            message: error.message,
+        });
+    }
+}
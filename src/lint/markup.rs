@@ -0,0 +1,21 @@
+//! Plain text that should be markup, see [`crate::markup`].
+
+use super::{Document, Finding, Rule, Severity};
+use crate::markup;
+
+pub const MARKUP: Rule = Rule {
+    name: "markup",
+    default_severity: Severity::Hint,
+    check: check_markup,
+};
+
+fn check_markup(document: &Document, findings: &mut Vec<Finding>) {
+    let rules = markup::rules();
+    for m in markup::find(document.source, &rules) {
+        findings.push(Finding {
+            line: m.line,
+            column: m.column,
+            message: format!("{}: {} ({})", m.rule.name, m.rule.message, m.replacement),
+        });
+    }
+}
@@ -0,0 +1,174 @@
+//! Client-side checks for MDN content.
+//!
+//! These complement the diagnostics produced by rari with the kind of
+//! feedback writers otherwise only get during review.
+
+mod accessibility;
+mod code;
+mod front_matter;
+mod links;
+mod macros;
+mod markup;
+mod sections;
+mod style;
+mod typography;
+
+use std::{collections::HashMap, fmt};
+
+use serde::Deserialize;
+
+use crate::{index::ContentIndex, macros::Registry, metadata::Page};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Off,
+    Hint,
+    Warning,
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Severity::Off => "off",
+            Severity::Hint => "hint",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        })
+    }
+}
+
+/// Per rule severity overrides, keyed by rule name.
+pub type LintConfig = HashMap<String, Severity>;
+
+pub struct Rule {
+    pub name: &'static str,
+    pub default_severity: Severity,
+    pub check: fn(&Document, &mut Vec<Finding>),
+}
+
+/// The page being linted.
+pub struct Document<'a> {
+    pub source: &'a str,
+    pub registry: &'a Registry,
+    /// For checks against the content tree, skipped without one.
+    pub index: Option<&'a ContentIndex>,
+    /// Lowercase locale of the page, for locale specific rules.
+    pub locale: Option<&'a str>,
+    /// Titles of the pages in the tree, for checks of link texts.
+    pub pages: Option<&'a [Page]>,
+    /// Local checkouts of example repositories by `owner/name`, see
+    /// [`crate::example_repos`].
+    pub example_repos: &'a HashMap<String, String>,
+}
+
+/// A problem found by a rule, before severity is applied.
+pub struct Finding {
+    /// Zero based line.
+    pub line: usize,
+    /// Zero based byte column.
+    pub column: usize,
+    pub message: String,
+}
+
+pub struct Diagnostic {
+    pub rule: &'static str,
+    pub severity: Severity,
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}: {} [{}] {}",
+            self.line + 1,
+            self.column + 1,
+            self.severity,
+            self.rule,
+            self.message
+        )
+    }
+}
+
+/// Counts by severity, most severe first, like `3 issues (1 error, 2 hints)`.
+pub fn summary(diagnostics: &[Diagnostic]) -> String {
+    if diagnostics.is_empty() {
+        return "no issues".to_string();
+    }
+    let counts: Vec<String> = [Severity::Error, Severity::Warning, Severity::Hint]
+        .into_iter()
+        .filter_map(|severity| {
+            let count = diagnostics
+                .iter()
+                .filter(|diagnostic| diagnostic.severity == severity)
+                .count();
+            let plural = if count == 1 { "" } else { "s" };
+            (count > 0).then(|| format!("{count} {severity}{plural}"))
+        })
+        .collect();
+    let plural = if diagnostics.len() == 1 { "" } else { "s" };
+    format!(
+        "{} issue{plural} ({})",
+        diagnostics.len(),
+        counts.join(", ")
+    )
+}
+
+const RULES: &[Rule] = &[
+    style::SECOND_PERSON,
+    style::FILLER_WORDS,
+    style::AMERICAN_SPELLING,
+    macros::UNKNOWN_MACRO,
+    macros::MACRO_ARGUMENTS,
+    macros::DEPRECATED_MACRO,
+    macros::MACRO_DELIMITERS,
+    sections::MISSING_SECTION,
+    sections::DUPLICATE_HEADING,
+    front_matter::KEY_ORDER_RULE,
+    front_matter::SHORT_TITLE,
+    front_matter::SCHEMA,
+    front_matter::TITLE_HEADING,
+    front_matter::TITLE_FORMAT,
+    front_matter::PAGE_TYPE_AREA,
+    links::BROKEN_LINK,
+    links::GLOSSARY_LINK,
+    links::FRAGMENT_LINK,
+    links::LINK_TITLE,
+    links::EXAMPLE_LINK,
+    code::CODE_ATTRIBUTION,
+    accessibility::IMAGE_ALT,
+    accessibility::TABLE_HEADER,
+    accessibility::LINK_TEXT,
+    accessibility::COLOR_ONLY,
+    markup::MARKUP,
+    typography::FRENCH_SPACING,
+    typography::CJK_SPACING,
+];
+
+pub fn lint(document: &Document, config: &LintConfig) -> Vec<Diagnostic> {
+    let mut diagnostics = vec![];
+    for rule in RULES {
+        let severity = config
+            .get(rule.name)
+            .copied()
+            .unwrap_or(rule.default_severity);
+        if severity == Severity::Off {
+            continue;
+        }
+        let mut findings = vec![];
+        (rule.check)(document, &mut findings);
+        diagnostics.extend(findings.into_iter().map(|finding| Diagnostic {
+            rule: rule.name,
+            severity,
+            line: finding.line,
+            column: finding.column,
+            message: finding.message,
+        }));
+    }
+    diagnostics.sort_by_key(|diagnostic| (diagnostic.line, diagnostic.column));
+    diagnostics
+}
@@ -0,0 +1,46 @@
+//! Checks of the page structure against its page type's template.
+
+use super::{Document, Finding, Rule, Severity};
+use crate::{anchors, front_matter::FrontMatter, page_types};
+
+pub const MISSING_SECTION: Rule = Rule {
+    name: "missing-section",
+    default_severity: Severity::Hint,
+    check: missing_section,
+};
+
+pub const DUPLICATE_HEADING: Rule = Rule {
+    name: "duplicate-heading",
+    default_severity: Severity::Warning,
+    check: duplicate_heading,
+};
+
+fn missing_section(document: &Document, findings: &mut Vec<Finding>) {
+    let Some(page_type) = FrontMatter::parse(document.source).and_then(|fm| fm.page_type()) else {
+        return;
+    };
+    let Some(template) = page_types::template(&page_type) else {
+        return;
+    };
+    for section in template.missing(document.source) {
+        findings.push(Finding {
+            line: 0,
+            column: 0,
+            message: format!("{page_type} pages need a \"{}\" section", section.heading),
+        });
+    }
+}
+
+fn duplicate_heading(document: &Document, findings: &mut Vec<Finding>) {
+    for duplicate in anchors::duplicate_headings(document.source) {
+        findings.push(Finding {
+            line: duplicate.heading.line,
+            column: 0,
+            message: format!(
+                "repeats an earlier heading, its anchor #{} breaks when headings are added \
+                 before it, rename it like \"{}\"",
+                duplicate.id, duplicate.renamed
+            ),
+        });
+    }
+}
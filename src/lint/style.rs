@@ -0,0 +1,98 @@
+//! MDN writing style guide rules.
+
+use super::{Document, Finding, Rule, Severity};
+use crate::markdown::{find_word, prose_lines};
+
+pub const SECOND_PERSON: Rule = Rule {
+    name: "second-person",
+    default_severity: Severity::Hint,
+    check: second_person,
+};
+
+pub const FILLER_WORDS: Rule = Rule {
+    name: "filler-words",
+    default_severity: Severity::Warning,
+    check: filler_words,
+};
+
+pub const AMERICAN_SPELLING: Rule = Rule {
+    name: "american-spelling",
+    default_severity: Severity::Warning,
+    check: american_spelling,
+};
+
+const FIRST_PERSON: &[&str] = &[
+    "we", "we'll", "we're", "we've", "our", "ours", "us", "let's",
+];
+
+const FILLERS: &[&str] = &[
+    "simply",
+    "easily",
+    "just",
+    "obviously",
+    "of course",
+    "clearly",
+];
+
+const BRITISH_SPELLINGS: &[(&str, &str)] = &[
+    ("analyse", "analyze"),
+    ("behaviour", "behavior"),
+    ("behaviours", "behaviors"),
+    ("cancelled", "canceled"),
+    ("centre", "center"),
+    ("colour", "color"),
+    ("colours", "colors"),
+    ("favour", "favor"),
+    ("initialise", "initialize"),
+    ("licence", "license"),
+    ("modelling", "modeling"),
+    ("neighbour", "neighbor"),
+    ("normalise", "normalize"),
+    ("organise", "organize"),
+    ("recognise", "recognize"),
+    ("serialise", "serialize"),
+    ("travelled", "traveled"),
+    ("utilise", "utilize"),
+];
+
+fn second_person(document: &Document, findings: &mut Vec<Finding>) {
+    for prose in prose_lines(document.source) {
+        for word in FIRST_PERSON {
+            for column in find_word(&prose.text, word) {
+                findings.push(Finding {
+                    line: prose.line,
+                    column,
+                    message: format!("address the reader in second person instead of \"{word}\""),
+                });
+            }
+        }
+    }
+}
+
+fn filler_words(document: &Document, findings: &mut Vec<Finding>) {
+    for prose in prose_lines(document.source) {
+        for word in FILLERS {
+            for column in find_word(&prose.text, word) {
+                findings.push(Finding {
+                    line: prose.line,
+                    column,
+                    message: format!("avoid \"{word}\", it can make readers feel inadequate"),
+                });
+            }
+        }
+    }
+}
+
+fn american_spelling(document: &Document, findings: &mut Vec<Finding>) {
+    for prose in prose_lines(document.source) {
+        for (british, american) in BRITISH_SPELLINGS {
+            for column in find_word(&prose.text, british) {
+                findings.push(Finding {
+                    line: prose.line,
+                    column,
+                    message: format!("use American spelling \"{american}\""),
+                });
+            }
+        }
+    }
+}
@@ -0,0 +1,97 @@
+//! Typographic conventions of individual locales, only checked on pages of
+//! those locales.
+
+use super::{Document, Finding, Rule, Severity};
+use crate::markdown::{mask_markup, prose_lines};
+
+pub const FRENCH_SPACING: Rule = Rule {
+    name: "french-spacing",
+    default_severity: Severity::Hint,
+    check: french_spacing,
+};
+
+pub const CJK_SPACING: Rule = Rule {
+    name: "cjk-spacing",
+    default_severity: Severity::Hint,
+    check: cjk_spacing,
+};
+
+const FRENCH_LOCALES: &[&str] = &["fr"];
+
+/// Locales whose style guides ask for a space between CJK and Latin text.
+/// The Japanese and Korean guides don't.
+const CJK_SPACING_LOCALES: &[&str] = &["zh-cn", "zh-tw"];
+
+/// Punctuation French sets off with a (narrow) non-breaking space.
+const FRENCH_HIGH_PUNCTUATION: &[char] = &[':', ';', '!', '?', '»'];
+
+fn is_active(document: &Document, locales: &[&str]) -> bool {
+    document
+        .locale
+        .is_some_and(|locale| locales.contains(&locale))
+}
+
+fn french_spacing(document: &Document, findings: &mut Vec<Finding>) {
+    if !is_active(document, FRENCH_LOCALES) {
+        return;
+    }
+    for prose in prose_lines(document.source) {
+        if prose.text.trim_start().starts_with(['#', '|']) {
+            continue;
+        }
+        let text = mask_markup(&prose.text);
+        for (column, c) in text.char_indices() {
+            let before = text[..column].chars().next_back();
+            let after = text[column + c.len_utf8()..].chars().next();
+            // `:` also appears in URLs and times, only check punctuation at
+            // the end of a clause.
+            let needs_space = FRENCH_HIGH_PUNCTUATION.contains(&c)
+                && (c == '»' || after.is_none_or(char::is_whitespace));
+            if needs_space && matches!(before, Some(b) if b != '\u{a0}' && b != '\u{202f}') {
+                findings.push(Finding {
+                    line: prose.line,
+                    column,
+                    message: format!("use a non-breaking space (U+00A0 or U+202F) before \"{c}\""),
+                });
+            }
+            if c == '«' && after.is_some_and(|a| a != '\u{a0}' && a != '\u{202f}') {
+                findings.push(Finding {
+                    line: prose.line,
+                    column,
+                    message: "use a non-breaking space (U+00A0 or U+202F) after \"«\"".to_string(),
+                });
+            }
+        }
+    }
+}
+
+fn is_cjk(c: char) -> bool {
+    matches!(c,
+        '\u{4e00}'..='\u{9fff}'
+        | '\u{3400}'..='\u{4dbf}'
+        | '\u{3040}'..='\u{30ff}'
+        | '\u{ac00}'..='\u{d7af}')
+}
+
+fn cjk_spacing(document: &Document, findings: &mut Vec<Finding>) {
+    if !is_active(document, CJK_SPACING_LOCALES) {
+        return;
+    }
+    for prose in prose_lines(document.source) {
+        let text = mask_markup(&prose.text);
+        let mut previous: Option<char> = None;
+        for (column, c) in text.char_indices() {
+            if let Some(before) = previous {
+                let latin = |c: char| c.is_ascii_alphanumeric();
+                if is_cjk(before) && latin(c) || latin(before) && is_cjk(c) {
+                    findings.push(Finding {
+                        line: prose.line,
+                        column,
+                        message: "add a space between CJK and Latin text".to_string(),
+                    });
+                }
+            }
+            previous = Some(c);
+        }
+    }
+}
@@ -0,0 +1,129 @@
+//! Installs from a rari release archive on disk, for air-gapped machines.
+//!
+//! `zed::download_file` only fetches URLs, so the archive is unpacked with
+//! the system's `tar`, `unzip` or `gunzip`. The result goes through the same
+//! steps as a download: made executable, checked to run, optionally
+//! stripped and moved to the `install_dir`.
+
+use std::{env, fs};
+
+use serde::{Deserialize, Serialize};
+use zed_extension_api::{process::Command, Result};
+
+use crate::{install_dir, sha256, state::State};
+
+/// `stat` arguments printing the modification time and size of a file, for
+/// GNU and BSD `stat`.
+const STAMP_FORMATS: [[&str; 2]; 2] = [["-c", "%Y %s"], ["-f", "%m %z"]];
+
+/// The hash of the archive last installed from, so it is only hashed again
+/// once the file changes.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ArchiveHash {
+    pub path: String,
+    /// Modification time and size, see [`STAMP_FORMATS`].
+    pub stamp: String,
+    pub sha256: String,
+}
+
+/// The absolute path of the archive and the version directory for it,
+/// named by content so replacing the archive installs the new one.
+pub fn version_dir(archive: &str, environment: &[(String, String)]) -> Result<(String, String)> {
+    let archive = install_dir::expand_home(archive, environment)
+        .ok_or(format!("can't expand {archive} without HOME"))?;
+    if !archive.starts_with('/') {
+        return Err(format!(
+            "local_archive must be an absolute path, got {archive}"
+        ));
+    }
+    let sha256 = cached_sha256(&archive)?;
+    let version_dir = format!("rari-local-{}", &sha256[..12]);
+    Ok((archive, version_dir))
+}
+
+/// Unpacks `archive` into `version_dir` of the work directory, which must
+/// then hold `binary`. Release archives hold the binary at the top.
+pub fn extract(archive: &str, version_dir: &str, binary: &str) -> Result<()> {
+    let work_dir = env::current_dir().map_err(|e| format!("no work directory: {e}"))?;
+    let dir = work_dir.join(version_dir).to_string_lossy().to_string();
+    fs::create_dir_all(version_dir).map_err(|e| format!("failed to create {dir}: {e}"))?;
+    let binary_path = format!("{version_dir}/{binary}");
+    let unpacked = unpack(archive, &dir, &binary_path);
+    if unpacked.is_err() || fs::metadata(&binary_path).is_err() {
+        fs::remove_dir_all(version_dir).ok();
+        return Err(format!(
+            "failed to unpack {archive}, expected {binary} at its top: {}",
+            unpacked.err().unwrap_or_default()
+        ));
+    }
+    println!("mdn: unpacked {archive}");
+    Ok(())
+}
+
+/// The program and arguments unpacking `archive` into the absolute `dir`,
+/// by its extension, and whether they print the binary instead: a gzipped or
+/// plain binary isn't unpacked into a directory.
+pub fn unpack_command<'a>(archive: &'a str, dir: &'a str) -> (&'static str, Vec<&'a str>, bool) {
+    if archive.ends_with(".zip") {
+        ("unzip", vec!["-oq", archive, "-d", dir], false)
+    } else if archive.ends_with(".tar.gz") || archive.ends_with(".tgz") {
+        ("tar", vec!["-xzf", archive, "-C", dir], false)
+    } else if archive.ends_with(".gz") {
+        ("gunzip", vec!["-c", archive], true)
+    } else {
+        ("cat", vec![archive], true)
+    }
+}
+
+/// Unpacks `archive` into the absolute `dir`, or writes it to `binary` in
+/// the work directory, see [`unpack_command`].
+fn unpack(archive: &str, dir: &str, binary: &str) -> Result<()> {
+    let (program, args, prints_binary) = unpack_command(archive, dir);
+    let output = Command::new(program).args(args).output()?;
+    if output.status != Some(0) {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    if prints_binary {
+        fs::write(binary, output.stdout).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+fn cached_sha256(archive: &str) -> Result<String> {
+    let stamp = STAMP_FORMATS
+        .iter()
+        .find_map(|format| {
+            let output = Command::new("stat")
+                .args(*format)
+                .arg(archive)
+                .output()
+                .ok()?;
+            let stamp = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            (output.status == Some(0) && !stamp.is_empty()).then_some(stamp)
+        })
+        .ok_or(format!("can't read {archive}"))?;
+    let mut state = State::load();
+    if let Some(cached) = &state.local_archive {
+        if cached.path == archive && cached.stamp == stamp {
+            return Ok(cached.sha256.clone());
+        }
+    }
+    let sha256 = sha256(archive)?;
+    state.local_archive = Some(ArchiveHash {
+        path: archive.to_string(),
+        stamp,
+        sha256: sha256.clone(),
+    });
+    state.save();
+    Ok(sha256)
+}
+
+/// The SHA-256 of the file at the absolute `path`, read with `cat` as it is
+/// outside of the work directory.
+pub fn sha256(path: &str) -> Result<String> {
+    let output = Command::new("cat").arg(path).output()?;
+    if output.status != Some(0) {
+        return Err(format!("can't read {path}"));
+    }
+    Ok(sha256::hex_digest(&output.stdout))
+}
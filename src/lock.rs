@@ -0,0 +1,93 @@
+//! Lock files in the work directory, shared by every Zed instance running
+//! the extension.
+//!
+//! A lock file holds the time it was taken or last refreshed and a token of
+//! its holder, always written to a temporary file renamed over it. Stale
+//! locks of crashed instances are taken over the same way, never by removing
+//! and recreating them, and the token read back afterwards tells which of two
+//! instances doing so won. A lock without readable contents, left by a crash
+//! right after creating it, is as old as the file.
+
+use std::{
+    collections::hash_map::RandomState,
+    fs::{self, OpenOptions},
+    hash::{BuildHasher, Hasher},
+    time::UNIX_EPOCH,
+};
+
+use crate::state;
+
+/// A held lock, released when dropped.
+pub struct Lock {
+    path: String,
+    token: u64,
+}
+
+impl Lock {
+    /// Takes the lock `name` unless another instance holds it. Locks not
+    /// refreshed for `stale_after_secs` are left from a crashed instance and
+    /// taken over.
+    pub fn acquire(name: &str, stale_after_secs: u64) -> Option<Lock> {
+        let lock = Lock {
+            path: format!("{name}.lock"),
+            token: RandomState::new().build_hasher().finish(),
+        };
+        let created = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&lock.path)
+            .is_ok();
+        if created {
+            lock.replace().ok()?;
+            return lock.is_held().then_some(lock);
+        }
+        let taken_at = fs::read_to_string(&lock.path)
+            .ok()
+            .and_then(|contents| contents.split_whitespace().next()?.parse::<u64>().ok())
+            .or_else(|| {
+                let modified = fs::metadata(&lock.path).ok()?.modified().ok()?;
+                Some(modified.duration_since(UNIX_EPOCH).ok()?.as_secs())
+            })?;
+        if state::now().saturating_sub(taken_at) < stale_after_secs {
+            return None;
+        }
+        lock.replace().ok()?;
+        lock.is_held().then_some(lock)
+    }
+
+    /// Moves the time of the lock forward, so a long running holder isn't
+    /// taken for a crashed one.
+    pub fn refresh(&self) {
+        if self.is_held() {
+            self.replace().ok();
+        }
+    }
+
+    fn contents(&self) -> String {
+        format!("{} {:x}", state::now(), self.token)
+    }
+
+    /// Whether the lock file still has our token.
+    fn is_held(&self) -> bool {
+        fs::read_to_string(&self.path).is_ok_and(|contents| {
+            contents.split_whitespace().nth(1) == Some(format!("{:x}", self.token).as_str())
+        })
+    }
+
+    /// Atomically replaces the lock file with one of ours.
+    fn replace(&self) -> std::io::Result<()> {
+        let temp = format!("{}.{:x}", self.path, self.token);
+        fs::write(&temp, self.contents())?;
+        fs::rename(&temp, &self.path).inspect_err(|_| {
+            fs::remove_file(&temp).ok();
+        })
+    }
+}
+
+impl Drop for Lock {
+    fn drop(&mut self) {
+        if self.is_held() {
+            fs::remove_file(&self.path).ok();
+        }
+    }
+}
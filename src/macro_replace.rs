@@ -0,0 +1,131 @@
+//! Find and replace of a macro across the content tree.
+//!
+//! Calls are matched by name and, optionally, arguments:
+//! `htmlattrxref("href", *)` matches two argument calls whose first argument
+//! is `href`. Replacements are templates like in the macro registry and are
+//! applied with [`crate::patch`].
+
+use zed_extension_api::{Result, Worktree};
+
+use crate::{
+    git,
+    macros::{self, MacroCall, Replacement},
+    patch::{self, Hunk},
+};
+
+pub struct Pattern {
+    name: String,
+    /// Unquoted arguments, `*` matching any. `None` matches any arguments.
+    args: Option<Vec<String>>,
+}
+
+impl Pattern {
+    pub fn parse(pattern: &str) -> Result<Self> {
+        let pattern = pattern
+            .trim()
+            .trim_start_matches("{{")
+            .trim_end_matches("}}");
+        let (name, args) = match pattern.split_once('(') {
+            Some((name, rest)) => {
+                let rest = rest
+                    .strip_suffix(')')
+                    .ok_or(format!("unbalanced parentheses in {pattern}"))?;
+                let args = macros::split_args(rest);
+                (
+                    name,
+                    Some(args.iter().map(|arg| unquote(arg).to_string()).collect()),
+                )
+            }
+            None => (pattern, None),
+        };
+        if name.trim().is_empty() {
+            return Err("missing macro name".to_string());
+        }
+        Ok(Pattern {
+            name: name.trim().to_string(),
+            args,
+        })
+    }
+
+    fn matches(&self, call: &MacroCall) -> bool {
+        if !call.name.eq_ignore_ascii_case(&self.name) {
+            return false;
+        }
+        let Some(expected) = &self.args else {
+            return true;
+        };
+        let args = call.args.as_deref().unwrap_or_default();
+        args.len() == expected.len()
+            && args
+                .iter()
+                .zip(expected)
+                .all(|(arg, expected)| expected == "*" || unquote(arg) == *expected)
+    }
+}
+
+fn unquote(arg: &str) -> &str {
+    arg.trim_matches(|c| c == '"' || c == '\'')
+}
+
+pub struct Edit {
+    pub path: String,
+    /// Zero based line.
+    pub line: usize,
+    pub before: String,
+    pub after: String,
+    /// Whether this is the last line and the file doesn't end with a newline.
+    pub at_end: bool,
+}
+
+/// Every line in the content tree with a call matching `pattern`, rewritten
+/// with `template`. Without a template the lines are returned unchanged.
+pub fn find(worktree: &Worktree, pattern: &Pattern, template: Option<&str>) -> Result<Vec<Edit>> {
+    let root = worktree.root_path();
+    let needle = format!("{{{{{}", pattern.name);
+    // git grep exits with 1 when nothing matches.
+    let paths = git::run(
+        &root,
+        &["grep", "-l", "-i", "-F", "-e", &needle, "--", "files/*.md"],
+    )
+    .unwrap_or_default();
+    let replacement = template.map(Replacement::new);
+    let mut edits = vec![];
+    for path in paths.lines() {
+        let Ok(source) = worktree.read_text_file(path) else {
+            continue;
+        };
+        let lines: Vec<&str> = source.lines().collect();
+        let rewritten = macros::rewrite_calls(&source, |call| {
+            if !pattern.matches(call) {
+                return None;
+            }
+            Some(match &replacement {
+                Some(replacement) => replacement.apply(call.args.as_deref().unwrap_or_default()),
+                None => lines[call.line][call.column..call.end].to_string(),
+            })
+        });
+        edits.extend(rewritten.into_iter().map(|(line, after)| Edit {
+            path: path.to_string(),
+            line,
+            before: lines[line].to_string(),
+            after,
+            at_end: line + 1 == lines.len() && !source.ends_with('\n'),
+        }));
+    }
+    Ok(edits)
+}
+
+/// Applies `edits` to the worktree, see [`patch::apply`].
+pub fn apply(worktree: &Worktree, edits: &[Edit]) -> Result<()> {
+    let hunks: Vec<Hunk> = edits
+        .iter()
+        .map(|edit| Hunk {
+            path: edit.path.clone(),
+            start: edit.line,
+            old: vec![edit.before.clone()],
+            new: vec![edit.after.clone()],
+            at_end: edit.at_end,
+        })
+        .collect();
+    patch::apply(worktree, &hunks)
+}
@@ -0,0 +1,45 @@
+//! Developer mode: find the implementation of a macro in a rari checkout.
+
+use zed_extension_api::Result;
+
+use crate::git;
+
+/// Where rari keeps its macros.
+const TEMPLS_DIR: &str = "crates/rari-doc/src/templ/templs";
+
+/// `file:line` locations in `checkout` of functions implementing `macro_name`.
+/// rari names macro functions in snake case (`EmbedLiveSample` is
+/// `embed_live_sample`), so names are compared without case and underscores.
+pub fn locate(checkout: &str, macro_name: &str) -> Result<Vec<String>> {
+    let wanted = normalize(macro_name);
+    let matches = git::run(
+        checkout,
+        &[
+            "grep",
+            "-n",
+            "-E",
+            r"^\s*pub fn [A-Za-z0-9_]+",
+            "--",
+            TEMPLS_DIR,
+        ],
+    )?;
+    Ok(matches
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.splitn(3, ':');
+            let (file, number, code) = (parts.next()?, parts.next()?, parts.next()?);
+            let name = code.trim().strip_prefix("pub fn ")?;
+            let name = name.split(['(', '<']).next()?;
+            // `_any` variants are the entry points rari registers.
+            let name = name.strip_suffix("_any").unwrap_or(name);
+            (normalize(name) == wanted).then(|| format!("{checkout}/{file}:{number}"))
+        })
+        .collect())
+}
+
+fn normalize(name: &str) -> String {
+    name.chars()
+        .filter(|c| *c != '_' && *c != '-')
+        .flat_map(char::to_lowercase)
+        .collect()
+}
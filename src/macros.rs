@@ -0,0 +1,332 @@
+//! The macro registry and a scanner for `{{macro(args)}}` calls.
+
+use serde::Deserialize;
+use zed_extension_api::{serde_json, Worktree};
+
+use crate::{
+    markdown::{self, prose_lines},
+    settings::MdnSettings,
+};
+
+const BUILTIN_MACROS: &str = include_str!("../data/macros.json");
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ParamType {
+    String,
+    Number,
+    Boolean,
+}
+
+impl ParamType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ParamType::String => "string",
+            ParamType::Number => "number",
+            ParamType::Boolean => "boolean",
+        }
+    }
+
+    /// Whether the raw argument `arg` fits. Numbers and booleans are
+    /// accepted quoted as well, and strings as plain numbers, like
+    /// KumaScript did (`{{EmbedLiveSample("x", 600, 300)}}`).
+    pub fn accepts(&self, arg: &str) -> bool {
+        let quoted = arg.len() >= 2
+            && (arg.starts_with('"') && arg.ends_with('"')
+                || arg.starts_with('\'') && arg.ends_with('\''));
+        let unquoted = if quoted { &arg[1..arg.len() - 1] } else { arg };
+        match self {
+            ParamType::String => quoted || arg.parse::<f64>().is_ok(),
+            ParamType::Number => unquoted.parse::<f64>().is_ok(),
+            ParamType::Boolean => matches!(unquoted, "true" | "false" | "0" | "1"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Param {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub param_type: ParamType,
+    #[serde(default)]
+    pub optional: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MacroInfo {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub params: Vec<Param>,
+    #[serde(default)]
+    pub deprecated: bool,
+    /// Rewrites of deprecated macros, see [`MacroInfo::rewrite`].
+    #[serde(default)]
+    pub replacements: Vec<Replacement>,
+}
+
+/// A rewrite of a call, `$1`, `$2`, … standing for its unquoted arguments.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Replacement {
+    /// The number of arguments this applies to, any when unset.
+    pub args: Option<usize>,
+    pub template: String,
+}
+
+impl Replacement {
+    pub fn new(template: &str) -> Self {
+        Replacement {
+            args: None,
+            template: template.to_string(),
+        }
+    }
+
+    /// The template with `args` substituted.
+    pub fn apply(&self, args: &[String]) -> String {
+        // Backwards, so `$1` doesn't eat the start of `$10`.
+        let mut text = self.template.clone();
+        for (i, arg) in args.iter().enumerate().rev() {
+            let value = arg.trim_matches(|c| c == '"' || c == '\'');
+            text = text.replace(&format!("${}", i + 1), value);
+        }
+        text
+    }
+}
+
+pub struct Registry {
+    macros: Vec<MacroInfo>,
+}
+
+impl MacroInfo {
+    /// The modern replacement of a call with `args`.
+    pub fn rewrite(&self, args: &[String]) -> Option<String> {
+        let replacement = self
+            .replacements
+            .iter()
+            .find(|replacement| replacement.args == Some(args.len()))
+            .or_else(|| {
+                self.replacements
+                    .iter()
+                    .find(|replacement| replacement.args.is_none())
+            })?;
+        Some(replacement.apply(args))
+    }
+
+    /// `name(param: type, optional?: type)`.
+    pub fn signature(&self) -> String {
+        let params = self
+            .params
+            .iter()
+            .map(|param| {
+                let optional = if param.optional { "?" } else { "" };
+                format!("{}{optional}: {}", param.name, param.param_type.as_str())
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!("{}({params})", self.name)
+    }
+}
+
+impl Registry {
+    /// The bundled registry extended by the file configured in
+    /// `macro_registry`. Entries from that file replace built-ins of the
+    /// same name.
+    pub fn for_worktree(worktree: &Worktree, settings: &MdnSettings) -> Self {
+        let mut macros: Vec<MacroInfo> =
+            serde_json::from_str(BUILTIN_MACROS).expect("bundled macro registry is valid");
+        let custom: Vec<MacroInfo> = settings
+            .macro_registry
+            .as_deref()
+            .and_then(|path| worktree.read_text_file(path).ok())
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default();
+        for info in custom {
+            macros.retain(|existing| !existing.name.eq_ignore_ascii_case(&info.name));
+            macros.push(info);
+        }
+        Registry { macros }
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.macros.iter().map(|info| info.name.as_str())
+    }
+
+    /// Macro names are case-insensitive.
+    pub fn get(&self, name: &str) -> Option<&MacroInfo> {
+        self.macros
+            .iter()
+            .find(|info| info.name.eq_ignore_ascii_case(name))
+    }
+}
+
+pub struct MacroCall {
+    pub name: String,
+    /// Zero based line.
+    pub line: usize,
+    /// Zero based byte column of the opening `{{`.
+    pub column: usize,
+    /// Zero based byte column after the closing `}}`.
+    pub end: usize,
+    /// Raw arguments, quotes included. `None` without parentheses.
+    pub args: Option<Vec<String>>,
+}
+
+/// Splits `"a, b", 1` at the commas outside of quotes.
+pub fn split_args(args: &str) -> Vec<String> {
+    let mut parts = vec![];
+    let mut current = String::new();
+    let mut quote: Option<char> = None;
+    for c in args.chars() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(open), _) if c == open => quote = None,
+            (None, ',') => {
+                parts.push(current.trim().to_string());
+                current.clear();
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    if !current.trim().is_empty() || !parts.is_empty() {
+        parts.push(current.trim().to_string());
+    }
+    parts
+}
+
+/// All single line macro calls in prose.
+pub fn calls(source: &str) -> Vec<MacroCall> {
+    let mut calls = vec![];
+    for prose in prose_lines(source) {
+        let text = &prose.text;
+        let mut offset = 0;
+        while let Some(start) = text[offset..].find("{{").map(|i| i + offset) {
+            let Some(end) = text[start..].find("}}").map(|i| i + start) else {
+                break;
+            };
+            let inner = text[start + 2..end].trim();
+            let name = inner.split('(').next().unwrap_or_default().trim();
+            let args = inner
+                .split_once('(')
+                .and_then(|(_, rest)| rest.strip_suffix(')'))
+                .map(split_args);
+            if !name.is_empty() {
+                calls.push(MacroCall {
+                    name: name.to_string(),
+                    line: prose.line,
+                    column: start,
+                    end: end + 2,
+                    args,
+                });
+            }
+            offset = end + 2;
+        }
+    }
+    calls
+}
+
+/// A `{{`, `}}`, quote or parenthesis of a macro call without its
+/// counterpart.
+pub struct DelimiterError {
+    /// Zero based line.
+    pub line: usize,
+    /// Zero based byte column of the offending token.
+    pub column: usize,
+    pub message: String,
+}
+
+/// Unbalanced macro delimiters in prose, at the token that is off rather
+/// than where rendering breaks further down. Calls are single line, and
+/// `\{{` is an escaped brace.
+pub fn delimiter_errors(source: &str) -> Vec<DelimiterError> {
+    let mut errors = vec![];
+    for prose in prose_lines(source) {
+        let text = prose.text.as_bytes();
+        let mut error = |column: usize, message: &str| {
+            errors.push(DelimiterError {
+                line: prose.line,
+                column,
+                message: message.to_string(),
+            })
+        };
+        // Column of the open `{{`, and of open quotes and parentheses in it.
+        let mut open: Option<usize> = None;
+        let mut quote: Option<(u8, usize)> = None;
+        let mut parens: Vec<usize> = vec![];
+        let mut i = 0;
+        while i < text.len() {
+            let pair = text.get(i..i + 2);
+            match (open, quote, text[i]) {
+                (None, _, b'\\') if text.get(i + 1) == Some(&b'{') => i += 1,
+                (None, _, _) if pair == Some(b"{{") => {
+                    open = Some(i);
+                    i += 1;
+                }
+                (None, _, _) if pair == Some(b"}}") => {
+                    error(i, "`}}` without an opening `{{`");
+                    i += 1;
+                }
+                (Some(_), Some((q, _)), c) if c == q => quote = None,
+                (Some(_), Some(_), _) => {}
+                (Some(_), None, c @ (b'"' | b'\'')) => quote = Some((c, i)),
+                (Some(_), None, b'(') => parens.push(i),
+                (Some(_), None, b')') if parens.is_empty() => {
+                    error(i, "`)` without an opening `(` in this macro call")
+                }
+                (Some(_), None, b')') => {
+                    parens.pop();
+                }
+                (Some(start), None, _) if pair == Some(b"{{") => {
+                    error(start, "`{{` is not closed before the next macro call");
+                    parens.clear();
+                    open = Some(i);
+                    i += 1;
+                }
+                (Some(_), None, _) if pair == Some(b"}}") => {
+                    for &paren in &parens {
+                        error(paren, "`(` is not closed in this macro call");
+                    }
+                    parens.clear();
+                    open = None;
+                    i += 1;
+                }
+                _ => {}
+            }
+            i += 1;
+        }
+        match (open, quote) {
+            (Some(_), Some((_, column))) => {
+                error(column, "string is not closed in this macro call")
+            }
+            (Some(start), None) => error(start, "`{{` is not closed on this line"),
+            (None, _) => {}
+        }
+    }
+    errors
+}
+
+/// Lines of `source` with their deprecated macro calls rewritten, as zero
+/// based line number and new text.
+pub fn rewrite_deprecated(source: &str, registry: &Registry) -> Vec<(usize, String)> {
+    rewrite_calls(source, |call| {
+        registry
+            .get(&call.name)
+            .filter(|info| info.deprecated)
+            .and_then(|info| info.rewrite(call.args.as_deref().unwrap_or_default()))
+    })
+}
+
+/// Lines of `source` with the calls `rewrite` returns a replacement for
+/// rewritten, as zero based line number and new text.
+pub fn rewrite_calls(
+    source: &str,
+    rewrite: impl Fn(&MacroCall) -> Option<String>,
+) -> Vec<(usize, String)> {
+    let replacements = calls(source).into_iter().filter_map(|call| {
+        let replacement = rewrite(&call)?;
+        Some((call.line, call.column..call.end, replacement))
+    });
+    markdown::replace_in_lines(source, replacements)
+}
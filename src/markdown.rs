@@ -0,0 +1,232 @@
+//! Minimal Markdown helpers shared by the client-side checks.
+//!
+//! This is not a Markdown parser. It only knows enough structure to skip
+//! front-matter and code so prose checks don't fire on examples.
+
+use std::{cmp::Reverse, ops::Range};
+
+/// A line of prose with inline code spans masked out by spaces, so columns
+/// still match the source.
+pub struct ProseLine {
+    /// Zero based line number.
+    pub line: usize,
+    pub text: String,
+}
+
+/// Yields all lines outside of front-matter and fenced code blocks.
+pub fn prose_lines(source: &str) -> impl Iterator<Item = ProseLine> + '_ {
+    let mut in_front_matter = false;
+    let mut fence: Option<&str> = None;
+    source.lines().enumerate().filter_map(move |(line, text)| {
+        let trimmed = text.trim_start();
+        if line == 0 && text.trim_end() == "---" {
+            in_front_matter = true;
+            return None;
+        }
+        if in_front_matter {
+            if text.trim_end() == "---" {
+                in_front_matter = false;
+            }
+            return None;
+        }
+        if let Some(marker) = fence {
+            if trimmed.starts_with(marker) {
+                fence = None;
+            }
+            return None;
+        }
+        if trimmed.starts_with("```") {
+            fence = Some("```");
+            return None;
+        }
+        if trimmed.starts_with("~~~") {
+            fence = Some("~~~");
+            return None;
+        }
+        Some(ProseLine {
+            line,
+            text: mask_inline_code(text),
+        })
+    })
+}
+
+fn mask_inline_code(text: &str) -> String {
+    let mut masked = String::with_capacity(text.len());
+    let mut in_code = false;
+    for c in text.chars() {
+        if c == '`' {
+            in_code = !in_code;
+            masked.push(' ');
+        } else if in_code {
+            masked.extend(std::iter::repeat_n(' ', c.len_utf8()));
+        } else {
+            masked.push(c);
+        }
+    }
+    masked
+}
+
+/// `text` with macro calls, link targets and HTML tags masked out by
+/// spaces, so columns still match.
+pub fn mask_markup(text: &str) -> String {
+    let mut masked = text.to_string();
+    for (open, close) in [("{{", "}}"), ("](", ")"), ("<", ">")] {
+        let mut offset = 0;
+        while let Some(start) = masked[offset..].find(open).map(|i| offset + i) {
+            let Some(end) = masked[start..].find(close).map(|i| start + i + close.len()) else {
+                break;
+            };
+            let blank = " ".repeat(end - start);
+            masked.replace_range(start..end, &blank);
+            offset = end;
+        }
+    }
+    masked
+}
+
+/// Byte offsets of case-insensitive whole word matches of `word` in `text`.
+pub fn find_word(text: &str, word: &str) -> Vec<usize> {
+    let haystack = text.to_ascii_lowercase();
+    let needle = word.to_ascii_lowercase();
+    let is_word_byte = |b: u8| b.is_ascii_alphanumeric() || b == b'_' || b == b'-';
+    haystack
+        .match_indices(&needle)
+        .map(|(start, _)| start)
+        .filter(|&start| {
+            let end = start + needle.len();
+            let bytes = haystack.as_bytes();
+            (start == 0 || !is_word_byte(bytes[start - 1]))
+                && (end == bytes.len() || !is_word_byte(bytes[end]))
+        })
+        .collect()
+}
+
+/// An ATX heading outside of code.
+pub struct Heading {
+    /// Zero based line number.
+    pub line: usize,
+    pub level: usize,
+    pub text: String,
+}
+
+pub fn headings(source: &str) -> impl Iterator<Item = Heading> + '_ {
+    prose_lines(source).filter_map(|prose| {
+        let level = prose.text.chars().take_while(|c| *c == '#').count();
+        let text = prose.text.get(level..)?;
+        (level > 0 && text.starts_with(' ')).then(|| Heading {
+            line: prose.line,
+            level,
+            // Headings are prose, so inline code was masked: take the source.
+            text: source.lines().nth(prose.line).unwrap_or_default()[level..]
+                .trim()
+                .to_string(),
+        })
+    })
+}
+
+/// An inline link `[text](url)` outside of code.
+pub struct Link {
+    /// Zero based line number.
+    pub line: usize,
+    /// Zero based byte column of the `[`.
+    pub column: usize,
+    pub text: String,
+    pub url: String,
+    /// Whether this is an image, `![alt](src)`.
+    pub image: bool,
+}
+
+pub fn links(source: &str) -> Vec<Link> {
+    let lines: Vec<&str> = source.lines().collect();
+    let mut links = vec![];
+    for prose in prose_lines(source) {
+        let text = &prose.text;
+        let mut offset = 0;
+        while let Some(start) = text[offset..].find('[').map(|i| offset + i) {
+            offset = start + 1;
+            let Some(close) = text[start..].find("](").map(|i| start + i) else {
+                break;
+            };
+            let Some(end) = text[close..].find(')').map(|i| close + i) else {
+                break;
+            };
+            // A `[` inside the label starts the real link.
+            if text[start + 1..close].contains('[') {
+                continue;
+            }
+            links.push(Link {
+                line: prose.line,
+                column: start,
+                // Inline code was masked: take the label from the source.
+                text: lines[prose.line][start + 1..close].trim().to_string(),
+                url: text[close + 2..end].trim().to_string(),
+                image: text[..start].ends_with('!'),
+            });
+            offset = end + 1;
+        }
+    }
+    links
+}
+
+/// Lines of `source` with byte ranges replaced, as zero based line number
+/// and new text. Ranges on one line must not overlap.
+pub fn replace_in_lines(
+    source: &str,
+    replacements: impl IntoIterator<Item = (usize, Range<usize>, String)>,
+) -> Vec<(usize, String)> {
+    let lines: Vec<&str> = source.lines().collect();
+    let mut replacements: Vec<_> = replacements.into_iter().collect();
+    // Right to left, so earlier columns stay valid.
+    replacements.sort_by_key(|(line, range, _)| (*line, Reverse(range.start)));
+    let mut rewritten: Vec<(usize, String)> = vec![];
+    for (line, range, replacement) in replacements {
+        if rewritten.last().is_none_or(|(last, _)| *last != line) {
+            rewritten.push((line, lines[line].to_string()));
+        }
+        let text = &mut rewritten.last_mut().expect("just pushed").1;
+        text.replace_range(range, &replacement);
+    }
+    rewritten
+}
+
+/// A fenced code block.
+pub struct CodeBlock {
+    /// Zero based line of the opening fence.
+    pub start: usize,
+    /// Zero based line of the closing fence, the last line when unclosed.
+    pub end: usize,
+}
+
+impl CodeBlock {
+    /// Number of lines between the fences.
+    pub fn len(&self) -> usize {
+        self.end.saturating_sub(self.start + 1)
+    }
+}
+
+pub fn code_blocks(source: &str) -> Vec<CodeBlock> {
+    let mut blocks = vec![];
+    let mut open: Option<(usize, &str)> = None;
+    let mut last = 0;
+    for (line, text) in source.lines().enumerate() {
+        last = line;
+        let trimmed = text.trim_start();
+        match open {
+            Some((start, marker)) if trimmed.starts_with(marker) => {
+                blocks.push(CodeBlock { start, end: line });
+                open = None;
+            }
+            Some(_) => {}
+            None => {
+                open = ["```", "~~~"]
+                    .into_iter()
+                    .find(|marker| trimmed.starts_with(marker))
+                    .map(|marker| (line, marker));
+            }
+        }
+    }
+    if let Some((start, _)) = open {
+        blocks.push(CodeBlock { start, end: last });
+    }
+    blocks
+}
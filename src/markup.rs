@@ -0,0 +1,168 @@
+//! Plain text that should be markup, like `Ctrl+C` for `<kbd>` or `404 Not
+//! Found` for `{{HTTPStatus}}`, from `data/markup_rules.json`.
+//!
+//! Patterns are literal text with `{a|b|c}` groups matching one of the
+//! alternatives, where `A-Z` stands for one character of a range. The
+//! longest alternative wins, there is no backtracking. Each group is
+//! captured as `$1`, `$2`, … for the replacement.
+
+use serde::Deserialize;
+use zed_extension_api::serde_json;
+
+use crate::markdown::{self, mask_markup, prose_lines};
+
+const MARKUP_RULES: &str = include_str!("../data/markup_rules.json");
+
+#[derive(Debug, Deserialize)]
+pub struct MarkupRule {
+    pub name: String,
+    pub message: String,
+    pub pattern: String,
+    pub replacement: String,
+}
+
+enum Token {
+    Literal(String),
+    Group(Vec<String>),
+}
+
+/// A match of a rule in prose.
+pub struct Match<'a> {
+    pub rule: &'a MarkupRule,
+    /// Zero based line.
+    pub line: usize,
+    /// Zero based byte column.
+    pub column: usize,
+    pub len: usize,
+    pub replacement: String,
+}
+
+pub fn rules() -> Vec<MarkupRule> {
+    serde_json::from_str(MARKUP_RULES).expect("bundled markup rules are valid")
+}
+
+fn tokens(pattern: &str) -> Vec<Token> {
+    let mut tokens = vec![];
+    let mut rest = pattern;
+    while !rest.is_empty() {
+        match rest.find('{') {
+            Some(0) => {
+                let end = rest.find('}').unwrap_or(rest.len() - 1);
+                let alternatives = rest[1..end].split('|').map(String::from).collect();
+                tokens.push(Token::Group(alternatives));
+                rest = &rest[end + 1..];
+            }
+            Some(start) => {
+                tokens.push(Token::Literal(rest[..start].to_string()));
+                rest = &rest[start..];
+            }
+            None => {
+                tokens.push(Token::Literal(rest.to_string()));
+                rest = "";
+            }
+        }
+    }
+    tokens
+}
+
+/// Length of the longest alternative matching at the start of `text`.
+fn match_group(alternatives: &[String], text: &str) -> Option<usize> {
+    alternatives
+        .iter()
+        .filter_map(|alternative| {
+            let chars: Vec<char> = alternative.chars().collect();
+            match chars.as_slice() {
+                [from, '-', to] => text
+                    .chars()
+                    .next()
+                    .filter(|c| (*from..=*to).contains(c))
+                    .map(char::len_utf8),
+                _ => text
+                    .starts_with(alternative.as_str())
+                    .then_some(alternative.len()),
+            }
+        })
+        .max()
+}
+
+/// Length and captures of a match of `tokens` at the start of `text`.
+fn match_at(tokens: &[Token], text: &str) -> Option<(usize, Vec<String>)> {
+    let mut len = 0;
+    let mut captures = vec![];
+    for token in tokens {
+        let rest = &text[len..];
+        match token {
+            Token::Literal(literal) => {
+                if !rest.starts_with(literal.as_str()) {
+                    return None;
+                }
+                len += literal.len();
+            }
+            Token::Group(alternatives) => {
+                let group = match_group(alternatives, rest)?;
+                captures.push(rest[..group].to_string());
+                len += group;
+            }
+        }
+    }
+    Some((len, captures))
+}
+
+fn is_word_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_'
+}
+
+/// Matches of all `rules` in the prose of `source`, earlier rules first
+/// where matches overlap.
+pub fn find<'a>(source: &str, rules: &'a [MarkupRule]) -> Vec<Match<'a>> {
+    let mut matches: Vec<Match> = vec![];
+    for prose in prose_lines(source) {
+        let text = mask_markup(&prose.text);
+        let bytes = text.as_bytes();
+        for rule in rules {
+            let tokens = tokens(&rule.pattern);
+            for column in 0..text.len() {
+                if !text.is_char_boundary(column) || column > 0 && is_word_byte(bytes[column - 1]) {
+                    continue;
+                }
+                let Some((len, captures)) = match_at(&tokens, &text[column..]) else {
+                    continue;
+                };
+                let end = column + len;
+                let overlaps = matches.iter().any(|other| {
+                    other.line == prose.line
+                        && column < other.column + other.len
+                        && other.column < end
+                });
+                if len == 0 || overlaps || bytes.get(end).is_some_and(|b| is_word_byte(*b)) {
+                    continue;
+                }
+                // Backwards, so `$1` doesn't eat the start of `$10`.
+                let mut replacement = rule.replacement.clone();
+                for (i, capture) in captures.iter().enumerate().rev() {
+                    replacement = replacement.replace(&format!("${}", i + 1), capture);
+                }
+                matches.push(Match {
+                    rule,
+                    line: prose.line,
+                    column,
+                    len,
+                    replacement,
+                });
+            }
+        }
+    }
+    matches.sort_by_key(|m| (m.line, m.column));
+    matches
+}
+
+/// Lines of `source` with all matches replaced, as zero based line number
+/// and new text.
+pub fn rewrite(source: &str, matches: &[Match]) -> Vec<(usize, String)> {
+    markdown::replace_in_lines(
+        source,
+        matches
+            .iter()
+            .map(|m| (m.line, m.column..m.column + m.len, m.replacement.clone())),
+    )
+}
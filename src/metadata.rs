@@ -0,0 +1,125 @@
+//! Titles and slugs of all pages, for searching by title.
+//!
+//! Reading the front-matter of ~12k pages through the worktree takes too
+//! long for a slash command, so `git grep` collects it into a file in the
+//! work directory when the language server starts. Pages changed since are
+//! read individually on load, and a new `HEAD` triggers a full rebuild.
+
+use std::{
+    collections::BTreeMap,
+    env, fs,
+    hash::{DefaultHasher, Hash, Hasher},
+};
+
+use zed_extension_api::{Result, Worktree};
+
+use crate::{
+    front_matter::{self, FrontMatter},
+    git,
+};
+
+pub struct Page {
+    pub path: String,
+    pub slug: String,
+    pub title: String,
+}
+
+fn metadata_file(worktree: &Worktree) -> Result<String> {
+    let mut hasher = DefaultHasher::new();
+    worktree.root_path().hash(&mut hasher);
+    let dir = env::current_dir().map_err(|e| format!("no work directory: {e}"))?;
+    Ok(format!(
+        "{}/metadata-{:x}.txt",
+        dir.to_string_lossy(),
+        hasher.finish()
+    ))
+}
+
+/// Writes the metadata file and the commit it was built from to
+/// `<file>.head`. The `---` delimiters are collected as well, as only keys
+/// between the first two count.
+pub fn refresh(worktree: &Worktree) -> Result<()> {
+    let root = worktree.root_path();
+    let file = metadata_file(worktree)?;
+    let head = git::run(&root, &["rev-parse", "HEAD"])?;
+    // git grep exits with 1 when nothing matches.
+    let lines = git::run(
+        &root,
+        &[
+            "grep",
+            "-n",
+            "-E",
+            "^(---|title:|slug:)",
+            "--",
+            "files/*.md",
+        ],
+    )
+    .unwrap_or_default();
+    fs::write(&file, lines).map_err(|e| format!("failed to write {file}: {e}"))?;
+    fs::write(format!("{file}.head"), head).map_err(|e| format!("failed to write {file}: {e}"))
+}
+
+/// All pages, rebuilding the metadata file first when `HEAD` moved.
+pub fn load(worktree: &Worktree) -> Result<Vec<Page>> {
+    let file = metadata_file(worktree)?;
+    let root = worktree.root_path();
+    let built_from = fs::read_to_string(format!("{file}.head")).unwrap_or_default();
+    if git::run(&root, &["rev-parse", "HEAD"]).ok().as_deref() != Some(built_from.trim()) {
+        refresh(worktree)?;
+    }
+    let lines = fs::read_to_string(&file).map_err(|e| format!("failed to read {file}: {e}"))?;
+
+    // Lines look like `files/en-us/web/index.md:2:title: Web`, grouped by
+    // file. A page's front-matter opens with `---` on line 1 and ends at the
+    // next `---`, matches after it are from the body.
+    let mut pages: BTreeMap<String, (String, String)> = BTreeMap::new();
+    let mut open = None;
+    for line in lines.lines() {
+        let mut parts = line.splitn(3, ':');
+        let (Some(path), Some(number), Some(entry)) = (parts.next(), parts.next(), parts.next())
+        else {
+            continue;
+        };
+        if entry.starts_with("---") {
+            open = (number == "1").then_some(path);
+            continue;
+        }
+        let Some((key, value)) = entry.split_once(':').filter(|_| open == Some(path)) else {
+            continue;
+        };
+        let page = pages.entry(path.to_string()).or_default();
+        let value = front_matter::unquote(value);
+        match key {
+            "slug" => page.0 = value,
+            _ => page.1 = value,
+        }
+    }
+
+    // Uncommitted changes since the build.
+    let status = git::run(&root, &["status", "--porcelain", "--", "files/*.md"])?;
+    for path in status.lines().filter_map(|line| line.get(3..)) {
+        let front_matter = worktree
+            .read_text_file(path)
+            .ok()
+            .and_then(|source| FrontMatter::parse(&source));
+        match front_matter {
+            Some(front_matter) => {
+                pages.insert(
+                    path.to_string(),
+                    (
+                        front_matter.value("slug").unwrap_or_default(),
+                        front_matter.title().unwrap_or_default(),
+                    ),
+                );
+            }
+            None => {
+                pages.remove(path);
+            }
+        }
+    }
+
+    Ok(pages
+        .into_iter()
+        .map(|(path, (slug, title))| Page { path, slug, title })
+        .collect())
+}
@@ -0,0 +1,73 @@
+//! Opt-in usage metrics, written to `metrics.json` in the extension's work
+//! directory and nowhere else. Nothing is ever sent over the network, users
+//! attach the file to bug reports themselves.
+
+use std::{collections::BTreeMap, fs};
+
+use serde::{Deserialize, Serialize};
+use zed_extension_api::{serde_json, Worktree};
+
+use crate::{settings::MdnSettings, status::BinarySource};
+
+const METRICS_FILE: &str = "metrics.json";
+const KEPT_STARTUP_TIMES: usize = 20;
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Metrics {
+    pub installs: u64,
+    pub startups: u64,
+    /// Time to resolve the server command of the most recent startups.
+    pub startup_ms: Vec<u64>,
+    pub failures: BTreeMap<String, u64>,
+    /// Startups per [`crate::status::BinarySource`].
+    pub sources: BTreeMap<String, u64>,
+}
+
+impl Metrics {
+    pub fn record_startup(&mut self, ms: u64, source: BinarySource) {
+        self.startups += 1;
+        *self.sources.entry(source.to_string()).or_default() += 1;
+        self.startup_ms.push(ms);
+        let excess = self.startup_ms.len().saturating_sub(KEPT_STARTUP_TIMES);
+        self.startup_ms.drain(..excess);
+    }
+
+    pub fn record_failure(&mut self, error: &str) {
+        *self
+            .failures
+            .entry(categorize(error).to_string())
+            .or_default() += 1;
+    }
+}
+
+/// Applies `update` to the metrics file, unless the user did not opt in.
+pub fn record(worktree: &Worktree, update: impl FnOnce(&mut Metrics)) {
+    if !MdnSettings::for_worktree(worktree).metrics {
+        return;
+    }
+    let mut metrics: Metrics = fs::read_to_string(METRICS_FILE)
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default();
+    update(&mut metrics);
+    if let Ok(json) = serde_json::to_string_pretty(&metrics) {
+        fs::write(METRICS_FILE, json).ok();
+    }
+}
+
+fn categorize(error: &str) -> &'static str {
+    if error.starts_with("failed to download") {
+        "download"
+    } else if error.starts_with("unable to find") {
+        "missing-asset"
+    } else if error.starts_with("not enough free disk space") {
+        "disk-space"
+    } else if error.contains("not supported") {
+        "unsupported-platform"
+    } else if error.contains("release") {
+        "release-lookup"
+    } else {
+        "other"
+    }
+}
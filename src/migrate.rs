@@ -0,0 +1,135 @@
+//! Bulk edits of front-matter, like renaming a `page-type` across a
+//! section of the tree.
+
+use zed_extension_api::{Result, Worktree};
+
+use crate::{
+    front_matter::{self, FrontMatter},
+    glob,
+    index::ContentIndex,
+    patch::Hunk,
+};
+
+pub enum Transform {
+    /// `set <key> <value>`: set or add a key.
+    Set { key: String, value: String },
+    /// `replace <key> <from> <to>`: change a value where it is `from`.
+    Replace {
+        key: String,
+        from: String,
+        to: String,
+    },
+    /// `rename <key> <new-key>`: rename a key, keeping its value.
+    Rename { key: String, to: String },
+    /// `remove <key>`: drop a key and its nested lines.
+    Remove { key: String },
+}
+
+impl Transform {
+    pub fn parse(args: &[&str]) -> Result<Self> {
+        let owned = |arg: &&str| arg.to_string();
+        match args {
+            ["set", key, value @ ..] if !value.is_empty() => Ok(Transform::Set {
+                key: owned(key),
+                value: value.join(" "),
+            }),
+            ["replace", key, from, to] => Ok(Transform::Replace {
+                key: owned(key),
+                from: owned(from),
+                to: owned(to),
+            }),
+            ["rename", key, to] => Ok(Transform::Rename {
+                key: owned(key),
+                to: owned(to),
+            }),
+            ["remove", key] => Ok(Transform::Remove { key: owned(key) }),
+            _ => Err(
+                "expected set <key> <value>, replace <key> <from> <to>, rename <key> <new-key> \
+                 or remove <key>"
+                    .to_string(),
+            ),
+        }
+    }
+
+    /// Applies the transformation, returning whether anything changed.
+    fn apply(&self, front_matter: &mut FrontMatter) -> bool {
+        match self {
+            Transform::Set { key, value } => {
+                if front_matter.value(key).as_deref() == Some(value.as_str()) {
+                    return false;
+                }
+                front_matter.set(key, value);
+                true
+            }
+            Transform::Replace { key, from, to } => {
+                if front_matter.value(key).as_deref() != Some(from.as_str()) {
+                    return false;
+                }
+                front_matter.set(key, to);
+                true
+            }
+            Transform::Rename { key, to } => {
+                match front_matter
+                    .entries
+                    .iter_mut()
+                    .find(|entry| entry.key == *key)
+                {
+                    Some(entry) => {
+                        entry.key = to.clone();
+                        true
+                    }
+                    None => false,
+                }
+            }
+            Transform::Remove { key } => front_matter.remove(key),
+        }
+    }
+}
+
+/// The front-matter changes of `transform` on the documents matching
+/// `pattern`, one hunk per changed page.
+pub fn plan(
+    worktree: &Worktree,
+    index: &ContentIndex,
+    pattern: &str,
+    transform: &Transform,
+) -> Vec<Hunk> {
+    let mut hunks = vec![];
+    for path in index
+        .files
+        .iter()
+        .filter(|path| glob::matches(pattern, path))
+    {
+        let Ok(source) = worktree.read_text_file(path) else {
+            continue;
+        };
+        let Some(mut front_matter) = FrontMatter::parse(&source) else {
+            continue;
+        };
+        let Some(old) = front_matter::block(&source) else {
+            continue;
+        };
+        if !transform.apply(&mut front_matter) {
+            continue;
+        }
+        let mut old: Vec<String> = old.lines().map(String::from).collect();
+        let mut new: Vec<String> = front_matter.to_block().lines().map(String::from).collect();
+        // Only front-matter, without a final newline.
+        let mut at_end = old.len() == source.lines().count() && !source.ends_with('\n');
+        // Keep the hunk to the changed lines.
+        while old.len() > 1 && new.len() > 1 && old.last() == new.last() {
+            old.pop();
+            new.pop();
+            at_end = false;
+        }
+        let start = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+        hunks.push(Hunk {
+            path: path.clone(),
+            start,
+            old: old.split_off(start),
+            new: new.split_off(start),
+            at_end,
+        });
+    }
+    hunks
+}
@@ -0,0 +1,55 @@
+//! Sanity checks for a fresh checkout, run on first activation in a worktree.
+//!
+//! An incomplete checkout makes rari fail with a cascade of macro errors that
+//! say little about the cause, so we look for the usual suspects up front and
+//! report them together. rari still starts, most pages work without them.
+
+use zed_extension_api::Worktree;
+
+use crate::{content, git};
+
+/// What is missing from the checkout of `worktree`, to fix for full
+/// diagnostics.
+pub fn check(worktree: &Worktree) -> Vec<String> {
+    let root = content::repo_root(worktree);
+    let mut problems = vec![];
+
+    if let Some(prefix) = content::files_prefix(worktree) {
+        problems.push(format!(
+            "the worktree is {prefix} of the checkout, only rari supports that: \
+             slash commands, `documents` and client-side checks need the checkout root open"
+        ));
+    }
+
+    if worktree.read_text_file(".gitmodules").is_ok() {
+        if let Ok(status) = git::run(&root, &["submodule", "status"]) {
+            let missing: Vec<&str> = status
+                .lines()
+                .filter(|line| line.starts_with('-'))
+                .filter_map(|line| line.split_whitespace().nth(1))
+                .collect();
+            if !missing.is_empty() {
+                problems.push(format!(
+                    "submodules are not initialized ({}), run `git submodule update --init`",
+                    missing.join(", ")
+                ));
+            }
+        }
+    }
+
+    if worktree
+        .read_text_file(".gitattributes")
+        .is_ok_and(|attributes| attributes.contains("filter=lfs"))
+    {
+        match git::run(&root, &["lfs", "ls-files"]) {
+            Ok(files) if files.lines().any(|line| line.contains(" - ")) => problems.push(
+                "git LFS media is missing, run `git lfs install && git lfs pull`".to_string(),
+            ),
+            Err(_) => problems
+                .push("the repository uses git LFS but git-lfs is not installed".to_string()),
+            Ok(_) => {}
+        }
+    }
+
+    problems
+}
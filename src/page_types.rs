@@ -0,0 +1,143 @@
+//! The sections MDN's page templates expect per front-matter `page-type`,
+//! from `data/page_types.json`, and the page types legal per area of the
+//! tree, from `data/page_type_areas.json`.
+
+use serde::Deserialize;
+use zed_extension_api::serde_json;
+
+use crate::markdown::{self, Heading};
+
+const PAGE_TYPES: &str = include_str!("../data/page_types.json");
+const AREAS: &str = include_str!("../data/page_type_areas.json");
+
+#[derive(Debug, Deserialize)]
+pub struct PageTemplate {
+    pub page_type: String,
+    /// Format of the title, see [`PageTemplate::title_for`].
+    #[serde(default)]
+    pub title: Option<String>,
+    /// In template order.
+    pub sections: Vec<Section>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Section {
+    pub heading: String,
+    pub level: usize,
+    #[serde(default)]
+    pub required: bool,
+    /// Content of a new section, usually a macro call.
+    #[serde(default)]
+    pub body: Option<String>,
+}
+
+impl Section {
+    /// The heading and body of a new section, followed by a blank line.
+    pub fn skeleton(&self) -> String {
+        let heading = format!("{} {}", "#".repeat(self.level), self.heading);
+        match &self.body {
+            Some(body) => format!("{heading}\n\n{body}\n\n"),
+            None => format!("{heading}\n\n"),
+        }
+    }
+}
+
+pub fn template(page_type: &str) -> Option<PageTemplate> {
+    let templates: Vec<PageTemplate> =
+        serde_json::from_str(PAGE_TYPES).expect("bundled page types are valid");
+    templates
+        .into_iter()
+        .find(|template| template.page_type == page_type)
+}
+
+impl PageTemplate {
+    pub fn section(&self, heading: &str) -> Option<&Section> {
+        self.sections
+            .iter()
+            .find(|section| section.heading.eq_ignore_ascii_case(heading))
+    }
+
+    /// Zero based line to insert `section` at in `source`: before the first
+    /// heading of a section that follows it in the template, or at the end.
+    pub fn insertion_line(&self, section: &Section, source: &str) -> usize {
+        let later: Vec<&Section> = self
+            .sections
+            .iter()
+            .skip_while(|candidate| candidate.heading != section.heading)
+            .skip(1)
+            .collect();
+        markdown::headings(source)
+            .find(|heading| {
+                later
+                    .iter()
+                    .any(|later| later.heading.eq_ignore_ascii_case(&heading.text))
+            })
+            .map(|heading| heading.line)
+            .unwrap_or_else(|| source.lines().count())
+    }
+
+    /// The title format filled in for the page at `slug`: `{name}` is the
+    /// last slug segment (without `_event`), `{parent}` the one before. A
+    /// `*` stands for any text. `None` without a format, and for names
+    /// that don't map to the title, like `Symbol.iterator` or
+    /// `Heading_Elements`.
+    pub fn title_for(&self, slug: &str) -> Option<String> {
+        let format = self.title.as_deref()?;
+        let mut segments = slug.trim_end_matches('/').rsplit('/');
+        let last = segments.next()?;
+        let name = last.strip_suffix("_event").unwrap_or(last);
+        if name.contains(['.', '_', '@']) {
+            return None;
+        }
+        let title = format.replace("{name}", name);
+        if !title.contains("{parent}") {
+            return Some(title);
+        }
+        Some(title.replace("{parent}", segments.next()?))
+    }
+
+    /// Required sections without a heading in `source`.
+    pub fn missing<'a>(&'a self, source: &str) -> Vec<&'a Section> {
+        let headings: Vec<Heading> = markdown::headings(source).collect();
+        self.sections
+            .iter()
+            .filter(|section| section.required)
+            .filter(|section| {
+                !headings
+                    .iter()
+                    .any(|heading| heading.text.eq_ignore_ascii_case(&section.heading))
+            })
+            .collect()
+    }
+}
+
+/// The page types legal below a directory of the tree.
+#[derive(Debug, Deserialize)]
+pub struct Area {
+    /// Lowercase path below the locale directory, ending in `/`, like
+    /// `web/css/`.
+    pub prefix: String,
+    pub page_types: Vec<String>,
+}
+
+fn areas() -> Vec<Area> {
+    serde_json::from_str(AREAS).expect("bundled page type areas are valid")
+}
+
+/// The area of the page at `slug`, the one with the longest prefix the slug
+/// is below. `None` outside of all areas, where any page type goes.
+pub fn area(slug: &str) -> Option<Area> {
+    let slug = slug.trim_matches('/').to_lowercase();
+    areas()
+        .into_iter()
+        .filter(|area| slug.starts_with(&area.prefix))
+        .max_by_key(|area| area.prefix.len())
+}
+
+/// The slug of the page at a worktree relative `path` like
+/// `files/en-us/web/css/color/index.md`, lowercase.
+pub fn slug_of_path(path: &str) -> Option<String> {
+    let (_, rest) = path.strip_prefix("files/")?.split_once('/')?;
+    let dir = rest.rsplit_once('/').map_or("", |(dir, _)| dir);
+    Some(dir.to_lowercase())
+}
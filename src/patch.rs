@@ -0,0 +1,92 @@
+//! Edits to files in the worktree, written as a patch and applied with
+//! `git apply`. The extension can only write to its work directory, and
+//! going through git keeps bulk edits reviewable and easy to reset.
+
+use std::{env, fs};
+
+use zed_extension_api::{Result, Worktree};
+
+use crate::git;
+
+const PATCH_FILE: &str = "edits.patch";
+
+/// Lines `start..start + old.len()` of `path` replaced by `new`.
+pub struct Hunk {
+    pub path: String,
+    /// Zero based line.
+    pub start: usize,
+    pub old: Vec<String>,
+    pub new: Vec<String>,
+    /// Whether `old` ends at the end of a file without a final newline.
+    pub at_end: bool,
+}
+
+impl Hunk {
+    /// The hunk in unified diff format, without context.
+    pub fn to_diff(&self) -> String {
+        let no_newline = if self.at_end {
+            "\\ No newline at end of file\n"
+        } else {
+            ""
+        };
+        // An empty side is numbered by the line before it.
+        let number = |lines: &[String]| self.start + usize::from(!lines.is_empty());
+        let mut diff = format!(
+            "@@ -{},{} +{},{} @@\n",
+            number(&self.old),
+            self.old.len(),
+            number(&self.new),
+            self.new.len()
+        );
+        for line in &self.old {
+            diff.push_str(&format!("-{line}\n"));
+        }
+        if !self.old.is_empty() {
+            diff.push_str(no_newline);
+        }
+        for line in &self.new {
+            diff.push_str(&format!("+{line}\n"));
+        }
+        if !self.new.is_empty() {
+            diff.push_str(no_newline);
+        }
+        diff
+    }
+}
+
+/// All `hunks` as one patch, hunks of a file in order and together.
+pub fn to_patch(hunks: &[Hunk]) -> String {
+    let mut patch = String::new();
+    let mut current = None;
+    for hunk in hunks.iter().filter(|hunk| hunk.old != hunk.new) {
+        if current != Some(&hunk.path) {
+            patch.push_str(&format!("--- a/{0}\n+++ b/{0}\n", hunk.path));
+            current = Some(&hunk.path);
+        }
+        patch.push_str(&hunk.to_diff());
+    }
+    patch
+}
+
+/// Applies `hunks` to the worktree with `git apply`.
+pub fn apply(worktree: &Worktree, hunks: &[Hunk]) -> Result<()> {
+    let patch = to_patch(hunks);
+    if patch.is_empty() {
+        return Ok(());
+    }
+    fs::write(PATCH_FILE, patch).map_err(|e| format!("failed to write patch: {e}"))?;
+    let patch_path = env::current_dir()
+        .map_err(|e| format!("no work directory: {e}"))?
+        .join(PATCH_FILE);
+    let result = git::run(
+        &worktree.root_path(),
+        &[
+            "apply",
+            "--unidiff-zero",
+            "--whitespace=nowarn",
+            &patch_path.to_string_lossy(),
+        ],
+    );
+    fs::remove_file(PATCH_FILE).ok();
+    result.map(|_| ())
+}
@@ -0,0 +1,139 @@
+//! Normalization of the root paths handed to rari in environment variables
+//! like `CONTENT_ROOT`.
+//!
+//! rari joins these with page paths and compares the results, so trailing
+//! separators, `..` components, mixed separators and the verbatim `\\?\`
+//! prefixes Windows APIs return all make it miss pages.
+//!
+//! Symlinks are only resolved on request, see [`ResolveSymlinks`]: rari
+//! needs the real path when `files/` links to a shared content store, while
+//! other tools rely on the path through the link.
+
+use std::collections::HashMap;
+
+use serde::Deserialize;
+use zed_extension_api::{self as zed, process::Command};
+
+/// The `resolve_symlinks` setting: `true` or `false` for all roots, or per
+/// root name with an optional `default`, like
+/// `{ "default": true, "en_us_content_path": false }`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum ResolveSymlinks {
+    All(bool),
+    PerRoot(HashMap<String, bool>),
+}
+
+impl Default for ResolveSymlinks {
+    fn default() -> Self {
+        ResolveSymlinks::All(false)
+    }
+}
+
+impl ResolveSymlinks {
+    /// Whether the root `name`, like `content_root`, is resolved.
+    pub fn applies_to(&self, name: &str) -> bool {
+        match self {
+            ResolveSymlinks::All(resolve) => *resolve,
+            ResolveSymlinks::PerRoot(roots) => roots
+                .get(name)
+                .or_else(|| roots.get("default"))
+                .copied()
+                .unwrap_or(false),
+        }
+    }
+}
+
+/// The real path of `path`, or `path` itself when it can't be resolved. The
+/// extension can't look outside of its work directory, so `realpath`
+/// resolves it.
+pub fn resolve_symlinks(path: &str) -> String {
+    if zed::current_platform().0 == zed::Os::Windows {
+        return path.to_string();
+    }
+    Command::new("realpath")
+        .arg(path)
+        .output()
+        .ok()
+        .filter(|output| output.status == Some(0))
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .filter(|resolved| !resolved.is_empty())
+        .unwrap_or_else(|| path.to_string())
+}
+
+/// `path` with `.` and `..` resolved lexically, duplicate and trailing
+/// separators removed, and the separators of `platform`.
+pub fn normalize(path: &str, platform: zed::Os) -> String {
+    match platform {
+        zed::Os::Mac | zed::Os::Linux => normalize_unix(path),
+        zed::Os::Windows => normalize_windows(path),
+    }
+}
+
+/// Resolves `.` and `..` in `components`, never above the first `floor`
+/// components (the drive or share). Above the root of an absolute path `..`
+/// is the root itself.
+fn resolve_dots<'a>(
+    components: impl Iterator<Item = &'a str>,
+    floor: usize,
+    absolute: bool,
+) -> Vec<&'a str> {
+    let mut resolved: Vec<&str> = vec![];
+    for component in components {
+        match component {
+            "" | "." => {}
+            ".." if resolved.len() > floor && resolved.last() != Some(&"..") => {
+                resolved.pop();
+            }
+            ".." if absolute => {}
+            component => resolved.push(component),
+        }
+    }
+    resolved
+}
+
+fn normalize_unix(path: &str) -> String {
+    let absolute = path.starts_with('/');
+    let components = resolve_dots(path.split('/'), 0, absolute);
+    let joined = components.join("/");
+    match (absolute, joined.is_empty()) {
+        (true, _) => format!("/{joined}"),
+        (false, true) => ".".to_string(),
+        (false, false) => joined,
+    }
+}
+
+fn normalize_windows(path: &str) -> String {
+    let path = path.replace('/', "\\");
+    // `\\?\UNC\server\share` and `\\?\C:\` from canonicalization, and
+    // `/C:/` as some tools write drive paths.
+    let (prefix, rest) = if let Some(rest) = path.strip_prefix(r"\\?\UNC\") {
+        (r"\\", rest)
+    } else if let Some(rest) = path.strip_prefix(r"\\?\") {
+        ("", rest)
+    } else if let Some(rest) = path.strip_prefix(r"\\") {
+        (r"\\", rest)
+    } else if path.get(2..3) == Some(":") && path.starts_with('\\') {
+        ("", &path[1..])
+    } else {
+        ("", path.as_str())
+    };
+    // The drive, or the server and share of a UNC path.
+    let floor = if prefix.is_empty() {
+        usize::from(rest.get(1..2) == Some(":"))
+    } else {
+        2
+    };
+    // `\dir` is absolute on the current drive.
+    let rooted = prefix.is_empty() && floor == 0 && rest.starts_with('\\');
+    let components = resolve_dots(rest.split('\\'), floor, floor > 0 || rooted);
+    let joined = components.join("\\");
+    if rooted {
+        return format!("\\{joined}");
+    }
+    if floor == 1 && components.len() == 1 {
+        // `C:` alone is the current directory on that drive.
+        return format!("{joined}\\");
+    }
+    format!("{prefix}{joined}")
+}
@@ -0,0 +1,71 @@
+//! Bundles of settings for the common ways of working on MDN, selected with
+//! the `profile` setting. Settings set explicitly win over the profile's.
+
+use serde::Deserialize;
+use zed_extension_api::serde_json::{self, json, Map, Value};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Profile {
+    /// Writing en-US content: style guidance while typing.
+    Writer,
+    /// Translating: no en-US style rules, warnings when the checkout falls
+    /// behind the en-US sources.
+    Translator,
+    /// Reviewing and maintaining: structural problems are errors.
+    Maintainer,
+}
+
+fn preset(profile: Profile) -> Value {
+    match profile {
+        Profile::Writer => json!({
+            "lint": {
+                "second-person": "warning",
+                "filler-words": "warning",
+                "glossary-link": "hint",
+                "link-title": "hint",
+                "markup": "hint",
+            },
+        }),
+        Profile::Translator => json!({
+            "fetch_upstream": true,
+            "stale_after_days": 14,
+            "lint": {
+                "second-person": "off",
+                "filler-words": "off",
+                "american-spelling": "off",
+                "glossary-link": "off",
+                "missing-section": "off",
+            },
+        }),
+        Profile::Maintainer => json!({
+            "fetch_upstream": true,
+            "lint": {
+                "unknown-macro": "error",
+                "macro-arguments": "error",
+                "deprecated-macro": "warning",
+                "fragment-link": "error",
+                "duplicate-heading": "error",
+                "front-matter-schema": "error",
+                "front-matter-order": "warning",
+                "code-attribution": "warning",
+            },
+        }),
+    }
+}
+
+/// `settings` over the preset of `profile`. Maps like `lint` are merged per
+/// entry, so one rule override keeps the rest of the profile's.
+pub fn apply(profile: Profile, settings: Map<String, Value>) -> Map<String, Value> {
+    let mut merged: Map<String, Value> =
+        serde_json::from_value(preset(profile)).expect("presets are objects");
+    for (key, value) in settings {
+        match (merged.get_mut(&key), value) {
+            (Some(Value::Object(preset)), Value::Object(value)) => preset.extend(value),
+            (_, value) => {
+                merged.insert(key, value);
+            }
+        }
+    }
+    merged
+}
@@ -0,0 +1,54 @@
+//! Port selection for `rari serve`.
+//!
+//! WASI has no sockets, so a port counts as taken when something answers an
+//! HTTP request on it.
+
+use zed_extension_api::http_client::{HttpMethod, HttpRequest, RedirectPolicy};
+
+use crate::processes;
+
+pub const DEFAULT_PORT: u16 = 8083;
+
+const PORTS_TO_PROBE: u16 = 20;
+
+pub enum Port {
+    /// Nothing listens there yet.
+    Free(u16),
+    /// A preview server we started already serves on it.
+    Reuse(u16),
+}
+
+fn in_use(port: u16) -> bool {
+    HttpRequest::builder()
+        .method(HttpMethod::Head)
+        .url(format!("http://localhost:{port}/"))
+        .redirect_policy(RedirectPolicy::NoFollow)
+        .build()
+        .and_then(|request| request.fetch())
+        .is_ok()
+}
+
+/// Picks the port for a new preview server of `worktree_id`. A configured
+/// port is used as is, or reused when one of our preview servers holds it.
+/// Otherwise the first free port from [`DEFAULT_PORT`] upwards is taken.
+pub fn select(worktree_id: u64, configured: Option<u16>) -> Result<Port, String> {
+    let ours = |port: u16| {
+        processes::running_on(port).is_some_and(|process| process.label == processes::PREVIEW)
+    };
+    if let Some(port) = configured {
+        return match (in_use(port), ours(port)) {
+            (false, _) => Ok(Port::Free(port)),
+            (true, true) => Ok(Port::Reuse(port)),
+            (true, false) => Err(format!("preview port {port} is used by another program")),
+        };
+    }
+    if let Some(port) =
+        processes::running(worktree_id, processes::PREVIEW).and_then(|process| process.port)
+    {
+        return Ok(Port::Reuse(port));
+    }
+    (DEFAULT_PORT..DEFAULT_PORT + PORTS_TO_PROBE)
+        .find(|&port| !in_use(port))
+        .map(Port::Free)
+        .ok_or("no free port for the preview server".to_string())
+}
@@ -0,0 +1,168 @@
+//! Bookkeeping for auxiliary processes (preview servers and the like).
+//!
+//! Extensions can't hold on to child processes, so they're started detached
+//! and tracked by PID in `processes.json`, keyed by worktree. Zed doesn't tell
+//! extensions when a worktree closes, so leftovers of a previous session are
+//! torn down when the extension starts. A PID only counts as ours while it
+//! still runs the command that was started, after a reboot or once PIDs wrap
+//! around it can be anything.
+//!
+//! Zed's process API waits for every command to exit, so detaching and
+//! running a command in another directory take `sh`. It only ever runs the
+//! two scripts here, which `extension.toml` allows word for word and only
+//! with the programs that need them.
+
+use std::{fs, iter::once};
+
+use serde::{Deserialize, Serialize};
+use zed_extension_api::{self as zed, process::Command, serde_json, Result};
+
+const PROCESSES_FILE: &str = "processes.json";
+
+/// Label of `rari serve` processes.
+pub const PREVIEW: &str = "preview";
+
+/// Starts `"$0" "$@"` in the background and prints its PID.
+const DETACH_SCRIPT: &str = r#"nohup "$0" "$@" >/dev/null 2>&1 & echo $!"#;
+
+/// Runs `"$@"` in the directory `$0`.
+pub const IN_DIR_SCRIPT: &str = r#"cd "$0" && exec "$@""#;
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Tracked {
+    pub worktree_id: u64,
+    pub label: String,
+    pub pid: u32,
+    /// The port a server listens on.
+    #[serde(default)]
+    pub port: Option<u16>,
+    /// Program and arguments the process was started with, see
+    /// [`Tracked::is_running`].
+    #[serde(default)]
+    pub command: Vec<String>,
+}
+
+impl Tracked {
+    /// Whether the PID still runs the command that was started, by its
+    /// program and last argument in `ps`.
+    fn is_running(&self) -> bool {
+        let (Some(program), Some(last)) = (self.command.first(), self.command.last()) else {
+            return false;
+        };
+        let Ok(output) = Command::new("ps")
+            .args(["-ww", "-o", "command=", "-p", &self.pid.to_string()])
+            .output()
+        else {
+            return false;
+        };
+        let running = String::from_utf8_lossy(&output.stdout);
+        let running = running.trim();
+        output.status == Some(0)
+            && running.starts_with(program.as_str())
+            && running.ends_with(last.as_str())
+    }
+
+    fn kill(&self) {
+        if self.is_running() {
+            Command::new("kill").arg(self.pid.to_string()).output().ok();
+        }
+    }
+}
+
+fn load() -> Vec<Tracked> {
+    fs::read_to_string(PROCESSES_FILE)
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+fn store(processes: &[Tracked]) {
+    if let Ok(json) = serde_json::to_string_pretty(processes) {
+        fs::write(PROCESSES_FILE, json).ok();
+    }
+}
+
+/// Starts `program` detached and tracks it under `label` for the worktree,
+/// replacing a previous process with the same label.
+pub fn spawn(
+    worktree_id: u64,
+    label: &str,
+    port: Option<u16>,
+    program: &str,
+    args: &[&str],
+    env: &[(String, String)],
+) -> Result<u32> {
+    if zed::current_platform().0 == zed::Os::Windows {
+        return Err("background processes are not supported on Windows".to_string());
+    }
+    stop(worktree_id, label);
+
+    let output = Command::new("sh")
+        .args(["-c", DETACH_SCRIPT, program])
+        .args(args.iter().copied())
+        .envs(env.iter().cloned())
+        .output()?;
+    let pid: u32 = String::from_utf8_lossy(&output.stdout)
+        .trim()
+        .parse()
+        .map_err(|_| format!("failed to start {program}"))?;
+
+    let mut processes = load();
+    processes.push(Tracked {
+        worktree_id,
+        label: label.to_string(),
+        pid,
+        port,
+        command: once(program)
+            .chain(args.iter().copied())
+            .map(String::from)
+            .collect(),
+    });
+    store(&processes);
+    Ok(pid)
+}
+
+/// The live process tracked under `label`, forgetting dead ones.
+pub fn running(worktree_id: u64, label: &str) -> Option<Tracked> {
+    let mut processes = load();
+    processes.retain(Tracked::is_running);
+    store(&processes);
+    processes
+        .into_iter()
+        .find(|process| process.worktree_id == worktree_id && process.label == label)
+}
+
+/// The live process, of any worktree, listening on `port`.
+pub fn running_on(port: u16) -> Option<Tracked> {
+    load()
+        .into_iter()
+        .find(|process| process.port == Some(port) && process.is_running())
+}
+
+pub fn stop(worktree_id: u64, label: &str) -> bool {
+    let mut processes = load();
+    let mut stopped = false;
+    processes.retain(|process| {
+        let matches = process.worktree_id == worktree_id && process.label == label;
+        if matches {
+            process.kill();
+            stopped = true;
+        }
+        !matches
+    });
+    store(&processes);
+    stopped
+}
+
+/// Stops everything a previous session left running that still runs what
+/// was started.
+pub fn stop_all() {
+    let processes = load();
+    if processes.is_empty() {
+        return;
+    }
+    for process in processes {
+        process.kill();
+    }
+    store(&[]);
+}
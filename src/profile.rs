@@ -0,0 +1,56 @@
+//! Aggregates rari's per-macro render timings.
+//!
+//! rari emits tracing span close events like
+//! `INFO templ{ident=embedlivesample}: rari_doc::templ: close time.busy=1.2ms time.idle=3µs`
+//! when built with `RUST_LOG=rari_doc::templ=trace` and span events enabled.
+//! Releases that don't log span events yield no timings, which
+//! `/mdn-profile` reports as an error rather than an empty table.
+
+use std::collections::HashMap;
+
+#[derive(Default)]
+pub struct MacroTiming {
+    pub calls: usize,
+    pub total_ms: f64,
+    pub max_ms: f64,
+}
+
+/// Per macro timings, slowest total first.
+pub fn macro_timings(log: &str) -> Vec<(String, MacroTiming)> {
+    let mut timings: HashMap<String, MacroTiming> = HashMap::new();
+    for line in log.lines() {
+        let (Some(name), Some(ms)) = (field(line, "ident"), field(line, "time.busy")) else {
+            continue;
+        };
+        let Some(ms) = parse_duration_ms(ms) else {
+            continue;
+        };
+        let timing = timings.entry(name.to_lowercase()).or_default();
+        timing.calls += 1;
+        timing.total_ms += ms;
+        timing.max_ms = timing.max_ms.max(ms);
+    }
+    let mut timings: Vec<_> = timings.into_iter().collect();
+    timings.sort_by(|(_, a), (_, b)| b.total_ms.total_cmp(&a.total_ms));
+    timings
+}
+
+fn field<'a>(line: &'a str, name: &str) -> Option<&'a str> {
+    let start = line.find(&format!("{name}="))? + name.len() + 1;
+    let rest = &line[start..];
+    let end = rest.find([' ', '}', ',', ':']).unwrap_or(rest.len());
+    Some(rest[..end].trim_matches('"'))
+}
+
+fn parse_duration_ms(value: &str) -> Option<f64> {
+    let split = value.find(|c: char| !c.is_ascii_digit() && c != '.')?;
+    let (number, unit) = value.split_at(split);
+    let number: f64 = number.parse().ok()?;
+    Some(match unit {
+        "s" => number * 1000.0,
+        "ms" => number,
+        "µs" | "us" => number / 1000.0,
+        "ns" => number / 1_000_000.0,
+        _ => return None,
+    })
+}
@@ -0,0 +1,55 @@
+//! One-off invocations of the rari CLI, next to the long running LSP.
+
+use zed_extension_api::{self as zed, process::Command, Result, Worktree};
+
+use crate::{content, interactive_examples, settings::MdnSettings, shell_env};
+
+/// The shell environment plus the content and interactive examples roots,
+/// like the LSP gets.
+pub fn environment(worktree: &Worktree) -> Vec<(String, String)> {
+    let (platform, _) = zed::current_platform();
+    let mut env = match platform {
+        zed::Os::Mac | zed::Os::Linux => shell_env::get(worktree),
+        zed::Os::Windows => vec![],
+    };
+    env.push(("CONTENT_ROOT".to_string(), content::content_root(worktree)));
+    let settings = MdnSettings::for_worktree(worktree);
+    if let Some(root) = interactive_examples::root(worktree, &settings) {
+        env.push((interactive_examples::ENV.to_string(), root));
+    }
+    env
+}
+
+/// Runs `rari <args>` against the worktree's content root and returns
+/// whether it succeeded together with stdout and stderr combined.
+pub fn output(
+    binary: &str,
+    worktree: &Worktree,
+    args: &[&str],
+    env: &[(&str, &str)],
+) -> Result<(bool, String)> {
+    let output = Command::new(binary)
+        .args(args.iter().copied())
+        .envs(environment(worktree))
+        .envs(env.iter().copied())
+        .output()?;
+    let text = format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    Ok((output.status == Some(0), text))
+}
+
+/// Like [`output`], but failing on a non-zero exit.
+pub fn run(
+    binary: &str,
+    worktree: &Worktree,
+    args: &[&str],
+    env: &[(&str, &str)],
+) -> Result<String> {
+    match output(binary, worktree, args, env)? {
+        (true, text) => Ok(text),
+        (false, text) => Err(format!("rari {} failed:\n{text}", args.join(" "))),
+    }
+}
@@ -0,0 +1,41 @@
+//! Maps rari's redirect validation output onto `_redirects.txt` lines.
+
+use zed_extension_api::Worktree;
+
+/// Worktree relative path of a locale's redirects file.
+pub fn redirects_file(locale: &str) -> String {
+    format!("files/{}/_redirects.txt", locale.to_lowercase())
+}
+
+/// Rewrites every line of rari's output that mentions a document URL as
+/// `files/<locale>/_redirects.txt:<line>: <message>`, pointing at the
+/// redirect that contains the URL. Other lines are kept as they are.
+pub fn locate(worktree: &Worktree, output: &str) -> String {
+    let mut located = String::new();
+    for message in output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+    {
+        match find_redirect(worktree, message) {
+            Some((file, line)) => located.push_str(&format!("{file}:{line}: {message}\n")),
+            None => located.push_str(&format!("{message}\n")),
+        }
+    }
+    located
+}
+
+fn find_redirect(worktree: &Worktree, message: &str) -> Option<(String, usize)> {
+    message
+        .split(|c: char| c.is_whitespace() || matches!(c, '"' | '\'' | '`' | ',' | '(' | ')'))
+        .filter(|word| word.starts_with('/') && word.contains("/docs/"))
+        .find_map(|url| {
+            let locale = url.trim_start_matches('/').split('/').next()?;
+            let file = redirects_file(locale);
+            let redirects = worktree.read_text_file(&file).ok()?;
+            let line = redirects
+                .lines()
+                .position(|line| line.split('\t').any(|field| field.trim() == url))?;
+            Some((file, line + 1))
+        })
+}
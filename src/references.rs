@@ -0,0 +1,62 @@
+//! Pages linking to a page, by URL or through xref macros.
+
+use zed_extension_api::{Result, Worktree};
+
+use crate::{content::DocUrl, git, sidebar, xref};
+
+/// `file:line: text` of every link to `slug` in the content tree and the
+/// sidebars.
+pub fn find(worktree: &Worktree, slug: &str) -> Result<Vec<String>> {
+    let root = worktree.root_path();
+    let escaped: String = slug
+        .chars()
+        .flat_map(|c| {
+            let escape = ".[]()*+?{}|^$\\".contains(c).then_some('\\');
+            escape.into_iter().chain([c])
+        })
+        .collect();
+    // The slug must end there, `Web/API/Node` is no link to `Web/API/Node/name`.
+    let link = format!("/docs/{escaped}([#)\"' ]|$)");
+    let mut references = grep(&root, &["-E", "-e", &link])?;
+
+    let url = DocUrl {
+        locale: "en-US".to_string(),
+        slug: slug.to_string(),
+        fragment: None,
+    };
+    if let Some(call) = xref::from_url(&url, None) {
+        // `{{domxref("Node.name")}}` searched as `domxref("Node.name"`, with
+        // and without the `()` of methods.
+        let target = call.trim_start_matches('{').trim_end_matches(")}}");
+        let method = format!("{}()\"", target.trim_end_matches('"'));
+        references.extend(grep(&root, &["-F", "-e", target, "-e", &method])?);
+    }
+    // Sidebars link by bare slug.
+    let sidebars = git::run(&root, &["ls-files", "--", "files/sidebars/*.yaml"])?;
+    for path in sidebars.lines() {
+        let Ok(source) = worktree.read_text_file(path) else {
+            continue;
+        };
+        references.extend(
+            sidebar::links(&source)
+                .into_iter()
+                .filter(|link| link.slug.eq_ignore_ascii_case(slug))
+                .map(|link| format!("{path}:{}: /{}", link.line + 1, link.slug)),
+        );
+    }
+    references.sort();
+    references.dedup();
+    Ok(references)
+}
+
+fn grep(root: &str, pattern: &[&str]) -> Result<Vec<String>> {
+    let args: Vec<&str> = ["grep", "-n", "-i"]
+        .into_iter()
+        .chain(pattern.iter().copied())
+        .chain(["--", "files/*.md"])
+        .collect();
+    // git grep exits with 1 when nothing matches.
+    Ok(git::run(root, &args)
+        .map(|matches| matches.lines().map(String::from).collect())
+        .unwrap_or_default())
+}
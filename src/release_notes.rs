@@ -0,0 +1,68 @@
+//! Release notes of a managed rari upgrade, so writers learn when rendering
+//! changed underneath them. `latest_github_release` doesn't return the
+//! release body, so it is fetched from the GitHub API after the download.
+
+use serde::{Deserialize, Serialize};
+use zed_extension_api::{
+    http_client::{HttpMethod, HttpRequest, RedirectPolicy},
+    serde_json,
+};
+
+use crate::state::State;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReleaseNotes {
+    pub version: String,
+    pub body: String,
+    /// Whether `/mdn-release-notes` showed them already.
+    #[serde(default)]
+    pub read: bool,
+}
+
+fn fetch(version: &str) -> Option<String> {
+    let response = HttpRequest::builder()
+        .method(HttpMethod::Get)
+        .url(format!(
+            "https://api.github.com/repos/mdn/rari/releases/tags/{version}"
+        ))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "mdn-zed")
+        .redirect_policy(RedirectPolicy::FollowAll)
+        .build()
+        .ok()?
+        .fetch()
+        .ok()?;
+    let release: serde_json::Value = serde_json::from_slice(&response.body).ok()?;
+    release["body"].as_str().map(String::from)
+}
+
+/// Stores the notes of `version` after an upgrade to it. Without network
+/// access only the version is remembered.
+pub fn record_upgrade(state: &mut State, version: &str) {
+    let body = fetch(version)
+        .unwrap_or_else(|| format!("See https://github.com/mdn/rari/releases/tag/{version}"));
+    state.release_notes = Some(ReleaseNotes {
+        version: version.to_string(),
+        body,
+        read: false,
+    });
+    state.save();
+}
+
+/// The version of an upgrade whose notes weren't read yet.
+pub fn unread() -> Option<String> {
+    State::load()
+        .release_notes
+        .filter(|notes| !notes.read)
+        .map(|notes| notes.version)
+}
+
+/// The notes of the last upgrade, marking them read.
+pub fn read() -> Option<ReleaseNotes> {
+    let mut state = State::load();
+    let notes = state.release_notes.as_mut()?;
+    notes.read = true;
+    let notes = notes.clone();
+    state.save();
+    Some(notes)
+}
@@ -0,0 +1,34 @@
+//! Connecting to an already running `rari lsp --listen <port>` instead of
+//! spawning rari, for remote setups where the binary can't run locally.
+//!
+//! Zed only speaks to language servers over stdio, so the connection goes
+//! through a small bridge from stdio to TCP.
+
+use zed_extension_api::{Command, Result, Worktree};
+
+/// Bridges from stdio to TCP, in order of preference.
+const BRIDGES: &[&str] = &["nc", "ncat", "socat"];
+
+/// The command connecting Zed to the server at `address` (`host:port`).
+pub fn bridge(worktree: &Worktree, address: &str) -> Result<Command> {
+    let (host, port) = address
+        .rsplit_once(':')
+        .filter(|(host, port)| !host.is_empty() && port.parse::<u16>().is_ok())
+        .ok_or(format!(
+            "server_address must be host:port, got \"{address}\""
+        ))?;
+    let (name, path) = BRIDGES
+        .iter()
+        .find_map(|name| Some((*name, worktree.which(name)?)))
+        .ok_or("connecting to server_address needs nc, ncat or socat on the PATH")?;
+    let args = if name == "socat" {
+        vec!["-".to_string(), format!("TCP:{host}:{port}")]
+    } else {
+        vec![host.to_string(), port.to_string()]
+    };
+    Ok(Command {
+        command: path,
+        args,
+        env: vec![],
+    })
+}
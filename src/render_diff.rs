@@ -0,0 +1,97 @@
+//! Diffs of the rendered output of pages between `HEAD` and the working tree.
+//!
+//! rari needs the whole content tree to render a page, so `HEAD` is rendered
+//! from a detached git worktree kept in `<git dir>/mdn-render/head` and moved
+//! along with `HEAD` on every run, until [`clean`] removes it. The rendered
+//! output goes to the work directory.
+
+use std::{env, fs, io};
+
+use zed_extension_api::{self as zed, process::Command, Result, Worktree};
+
+use crate::{git, paths, rari};
+
+fn base(worktree: &Worktree) -> Result<String> {
+    let git_dir = git::run(&worktree.root_path(), &["rev-parse", "--absolute-git-dir"])?;
+    Ok(format!("{git_dir}/mdn-render/head"))
+}
+
+/// Renders `files` (worktree relative) at `HEAD` and in the working tree and
+/// returns the diff of the output, empty when rendering didn't change.
+pub fn diff(worktree: &Worktree, rari_binary: &str, files: &[String]) -> Result<String> {
+    let root = worktree.root_path();
+    let base = base(worktree)?;
+    let work_dir = env::current_dir().map_err(|e| format!("no work directory: {e}"))?;
+    let before = format!("{}/render-before", work_dir.display());
+    let after = format!("{}/render-after", work_dir.display());
+
+    if git::run(&base, &["rev-parse", "HEAD"]).is_err() {
+        git::run(&root, &["worktree", "add", "--detach", &base, "HEAD"])?;
+    } else {
+        let head = git::run(&root, &["rev-parse", "HEAD"])?;
+        git::run(&base, &["checkout", "--quiet", "--detach", &head])?;
+    }
+    remove_dir("render-before")?;
+    remove_dir("render-after")?;
+
+    render(worktree, rari_binary, &base, &before, files)?;
+    render(worktree, rari_binary, &root, &after, files)?;
+
+    // `--no-index` exits with 1 when there are differences.
+    let output = Command::new("git")
+        .args(["diff", "--no-index", "--no-color", &before, &after])
+        .output()?;
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .replace(&before, "HEAD")
+        .replace(&after, "working-tree"))
+}
+
+fn render(
+    worktree: &Worktree,
+    rari_binary: &str,
+    checkout: &str,
+    out: &str,
+    files: &[String],
+) -> Result<()> {
+    let content_root = paths::normalize(&format!("{checkout}/files"), zed::current_platform().0);
+    let out = &paths::normalize(out, zed::current_platform().0);
+    let paths: Vec<String> = files
+        .iter()
+        .map(|file| format!("{checkout}/{file}"))
+        .collect();
+    let mut args = vec!["build"];
+    for path in &paths {
+        args.extend(["--files", path]);
+    }
+    rari::run(
+        rari_binary,
+        worktree,
+        &args,
+        &[("CONTENT_ROOT", &content_root), ("BUILD_OUT_ROOT", out)],
+    )?;
+    Ok(())
+}
+
+/// Removes the worktree `HEAD` is rendered from, returning whether there
+/// was one.
+pub fn clean(worktree: &Worktree) -> Result<bool> {
+    let base = base(worktree)?;
+    if git::run(&base, &["rev-parse", "HEAD"]).is_err() {
+        return Ok(false);
+    }
+    git::run(
+        &worktree.root_path(),
+        &["worktree", "remove", "--force", &base],
+    )?;
+    Ok(true)
+}
+
+/// Removes `path` in the work directory.
+fn remove_dir(path: &str) -> Result<()> {
+    match fs::remove_dir_all(path) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => {
+            Err(format!("removing {path} failed: {e}"))
+        }
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,113 @@
+//! New pages from the templates of `data/page_types.json`, with the extra
+//! front-matter of `new_page_fields`.
+//!
+//! Field values are templates: `{author}` and `{email}` are the git identity
+//! of the checkout (`user.name` and `user.email`), `{date}` is today in UTC
+//! as `YYYY-MM-DD`, and `{title}` and `{slug}` are the new page's.
+
+use std::collections::HashMap;
+
+use zed_extension_api::Result;
+
+use crate::{front_matter::FrontMatter, git, page_types, state, titles};
+
+/// Who is writing the page, from git's config.
+pub struct Identity {
+    pub name: Option<String>,
+    pub email: Option<String>,
+}
+
+impl Identity {
+    /// The identity git commits with in `repo`, including the global config.
+    pub fn of(repo: &str) -> Self {
+        Identity {
+            name: git::config(repo, "user.name"),
+            email: git::config(repo, "user.email"),
+        }
+    }
+}
+
+/// A new page at `slug`: front-matter with its title in the format of
+/// `page_type` and `fields`, followed by the sections of the page type's
+/// template.
+pub fn page(
+    slug: &str,
+    page_type: Option<&str>,
+    fields: &HashMap<String, String>,
+    identity: &Identity,
+) -> Result<String> {
+    let slug = slug.trim_matches('/');
+    let template = page_type.and_then(page_types::template);
+    let name = slug.rsplit('/').next().unwrap_or(slug);
+    let title = template
+        .as_ref()
+        // A `*` for free text is left for the writer to fill in.
+        .and_then(|template| template.title_for(slug))
+        .unwrap_or_else(|| name.to_string());
+    let date = today();
+    let variables = [
+        ("author", identity.name.as_deref()),
+        ("email", identity.email.as_deref()),
+        ("date", Some(date.as_str())),
+        ("title", Some(title.as_str())),
+        ("slug", Some(slug)),
+    ];
+
+    let mut front_matter = FrontMatter { entries: vec![] };
+    front_matter.set("title", &titles::quote(&title));
+    front_matter.set("slug", slug);
+    if let Some(page_type) = page_type {
+        front_matter.set("page-type", page_type);
+    }
+    let mut keys: Vec<&String> = fields.keys().collect();
+    keys.sort();
+    for key in keys {
+        let mut value = fields[key].clone();
+        for (variable, replacement) in variables {
+            let placeholder = format!("{{{variable}}}");
+            if !value.contains(&placeholder) {
+                continue;
+            }
+            let replacement = replacement.ok_or(format!(
+                "`new_page_fields.{key}` uses {placeholder}, but git has no {}, \
+                 set it with `git config --global {0} <value>`",
+                match variable {
+                    "author" => "user.name",
+                    _ => "user.email",
+                }
+            ))?;
+            value = value.replace(&placeholder, replacement);
+        }
+        front_matter.set(key, &titles::quote(&value));
+    }
+
+    let mut text = front_matter.to_block();
+    text.push('\n');
+    for section in template.iter().flat_map(|template| &template.sections) {
+        text.push('\n');
+        text.push_str(section.skeleton().trim_end());
+        text.push('\n');
+    }
+    Ok(text)
+}
+
+/// Today in UTC as `YYYY-MM-DD`.
+fn today() -> String {
+    let days = (state::now() / (24 * 60 * 60)) as i64;
+    // Howard Hinnant's `civil_from_days`.
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
+}
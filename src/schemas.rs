@@ -0,0 +1,62 @@
+//! JSON schemas for MDN's YAML and JSON files, handed to the YAML and JSON
+//! language servers so they are validated even without rari.
+
+use std::{env, fs};
+
+use zed_extension_api::serde_json::{self, json};
+
+const SIDEBAR_SCHEMA: &str = include_str!("../schemas/sidebar.schema.json");
+const FRONT_MATTER_SCHEMA: &str = include_str!("../schemas/front-matter.schema.json");
+const BANNERS_SCHEMA: &str = include_str!("../schemas/banners.schema.json");
+const SIDEBAR_SCHEMA_FILE: &str = "mdn-sidebar.schema.json";
+const BANNERS_SCHEMA_FILE: &str = "mdn-banners.schema.json";
+
+/// Workspace configuration associating the schemas with their files.
+pub fn configuration(target_language_server_id: &str) -> Option<serde_json::Value> {
+    match target_language_server_id {
+        "yaml-language-server" => Some(json!({
+            "yaml": {
+                "schemas": {
+                    schema_url(SIDEBAR_SCHEMA_FILE, SIDEBAR_SCHEMA)?: ["files/sidebars/*.yaml"]
+                }
+            }
+        })),
+        "json-language-server" => Some(json!({
+            "json": {
+                "schemas": [{
+                    "fileMatch": ["banners.json"],
+                    "url": schema_url(BANNERS_SCHEMA_FILE, BANNERS_SCHEMA)?
+                }]
+            }
+        })),
+        _ => None,
+    }
+}
+
+/// The language servers only read schemas from URLs, so `schema` is written
+/// to `file` in the work directory.
+fn schema_url(file: &str, schema: &str) -> Option<String> {
+    fs::write(file, schema).ok()?;
+    let path = env::current_dir().ok()?.join(file);
+    Some(format!("file://{}", path.to_string_lossy()))
+}
+
+/// Keys of the front-matter schema and which of them are required.
+pub fn front_matter_keys() -> (Vec<String>, Vec<String>) {
+    let schema: serde_json::Value =
+        serde_json::from_str(FRONT_MATTER_SCHEMA).expect("bundled schema is valid");
+    let keys = schema["properties"]
+        .as_object()
+        .map(|properties| properties.keys().cloned().collect())
+        .unwrap_or_default();
+    let required = schema["required"]
+        .as_array()
+        .map(|required| {
+            required
+                .iter()
+                .filter_map(|key| key.as_str().map(String::from))
+                .collect()
+        })
+        .unwrap_or_default();
+    (keys, required)
+}
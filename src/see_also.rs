@@ -0,0 +1,159 @@
+//! Candidates for a page's "See also" section, from the content tree alone:
+//! the parent page and siblings (the interface of an API member, the other
+//! members), pages implementing the same specification (the CSS module) and
+//! the neighbours in a sidebar group.
+
+use std::collections::HashSet;
+
+use zed_extension_api::{Result, Worktree};
+
+use crate::{front_matter::FrontMatter, git, metadata::Page, sidebar};
+
+/// Per kind of relation, so siblings of a large interface don't crowd out
+/// the rest.
+const MAX_PER_REASON: usize = 10;
+
+pub struct Suggestion {
+    pub slug: String,
+    pub title: String,
+    pub reason: &'static str,
+}
+
+impl Suggestion {
+    pub fn list_item(&self) -> String {
+        format!("- [{}](/en-US/docs/{})", self.title, self.slug)
+    }
+}
+
+/// Related pages of the en-US page at `path` that it doesn't link yet.
+pub fn suggest(worktree: &Worktree, path: &str, pages: &[Page]) -> Result<Vec<Suggestion>> {
+    let source = worktree.read_text_file(path)?;
+    let front_matter = FrontMatter::parse(&source).ok_or(format!("{path} has no front-matter"))?;
+    let slug = front_matter
+        .value("slug")
+        .ok_or(format!("{path} has no slug"))?;
+    let lowercase = source.to_lowercase();
+    let linked = |candidate: &str| {
+        candidate.eq_ignore_ascii_case(&slug)
+            || lowercase.contains(&format!("/docs/{}", candidate.to_lowercase()))
+    };
+    let page_for = |candidate: &str| {
+        pages.iter().find(|page| {
+            page.slug.eq_ignore_ascii_case(candidate) && page.path.starts_with("files/en-us/")
+        })
+    };
+
+    let mut candidates: Vec<(&str, &'static str)> = vec![];
+    if let Some((parent, _)) = slug.rsplit_once('/') {
+        candidates.push((parent, "parent"));
+        let prefix = format!("{}/", parent.to_lowercase());
+        candidates.extend(
+            pages
+                .iter()
+                .filter(|page| page.path.starts_with("files/en-us/"))
+                .filter(|page| {
+                    let lower = page.slug.to_lowercase();
+                    lower
+                        .strip_prefix(&prefix)
+                        .is_some_and(|rest| !rest.contains('/'))
+                })
+                .map(|page| (page.slug.as_str(), "sibling")),
+        );
+    }
+    let root = worktree.root_path();
+    for spec in spec_urls(&front_matter) {
+        // git grep exits with 1 when nothing matches.
+        let paths = git::run(
+            &root,
+            &["grep", "-l", "-F", "-e", &spec, "--", "files/en-us/*.md"],
+        )
+        .unwrap_or_default();
+        let paths: HashSet<&str> = paths.lines().collect();
+        candidates.extend(
+            pages
+                .iter()
+                .filter(|page| paths.contains(page.path.as_str()))
+                .map(|page| (page.slug.as_str(), "same specification")),
+        );
+    }
+    for sidebar_path in git::run(&root, &["ls-files", "--", "files/sidebars/*.yaml"])?.lines() {
+        let Ok(sidebar) = worktree.read_text_file(sidebar_path) else {
+            continue;
+        };
+        for neighbour in sidebar_group(&sidebar, &slug) {
+            if let Some(page) = page_for(&neighbour) {
+                candidates.push((page.slug.as_str(), "same sidebar group"));
+            }
+        }
+    }
+
+    let mut suggestions: Vec<Suggestion> = vec![];
+    for (candidate, reason) in candidates {
+        if linked(candidate)
+            || suggestions
+                .iter()
+                .any(|suggestion| suggestion.slug.eq_ignore_ascii_case(candidate))
+            || suggestions
+                .iter()
+                .filter(|suggestion| suggestion.reason == reason)
+                .count()
+                >= MAX_PER_REASON
+        {
+            continue;
+        }
+        let Some(page) = page_for(candidate) else {
+            continue;
+        };
+        suggestions.push(Suggestion {
+            slug: page.slug.clone(),
+            title: page.title.clone(),
+            reason,
+        });
+    }
+    Ok(suggestions)
+}
+
+/// The `spec-urls` of a page without fragments, which point into one
+/// specification like a CSS module.
+fn spec_urls(front_matter: &FrontMatter) -> Vec<String> {
+    let Some(entry) = front_matter.get("spec-urls") else {
+        return vec![];
+    };
+    let mut urls: Vec<String> = [entry.value.as_str()]
+        .into_iter()
+        .chain(entry.nested.iter().map(String::as_str))
+        .map(|line| line.trim().trim_start_matches('-').trim())
+        .filter(|url| url.starts_with("http"))
+        .map(|url| url.split('#').next().unwrap_or(url).to_string())
+        .collect();
+    urls.sort();
+    urls.dedup();
+    urls
+}
+
+/// The other pages of the sidebar group listing `slug`: the entries at the
+/// same or deeper indentation around it.
+fn sidebar_group(sidebar: &str, slug: &str) -> Vec<String> {
+    let lines: Vec<&str> = sidebar.lines().collect();
+    let indent = |line: &str| line.len() - line.trim_start().len();
+    let links = sidebar::links(sidebar);
+    let Some(own) = links
+        .iter()
+        .find(|link| link.slug.eq_ignore_ascii_case(slug))
+        .map(|link| link.line)
+    else {
+        return vec![];
+    };
+    let level = indent(lines[own]);
+    let in_group = |line: &usize| lines[*line].trim().is_empty() || indent(lines[*line]) >= level;
+    let start = (0..own).rev().take_while(in_group).last().unwrap_or(own);
+    let end = (own + 1..lines.len())
+        .take_while(in_group)
+        .last()
+        .unwrap_or(own);
+    links
+        .into_iter()
+        .filter(|link| (start..=end).contains(&link.line) && link.line != own)
+        .map(|link| link.slug)
+        .collect()
+}
@@ -0,0 +1,340 @@
+use std::{collections::HashMap, iter::once};
+
+use serde::Deserialize;
+use zed_extension_api::{serde_json, settings::LspSettings, Worktree};
+
+use crate::{
+    assets::AssetOverride,
+    container::ContainerSettings,
+    content,
+    lint::LintConfig,
+    paths::ResolveSymlinks,
+    presets::{self, Profile},
+    schemas,
+    shell_env::ShellEnvMode,
+};
+
+pub const LSP_SETTINGS_KEY: &str = "mdn-lsp";
+
+/// Server ids rari is configured under in other editors, read when there is
+/// nothing under [`LSP_SETTINGS_KEY`], in this order.
+const LEGACY_LSP_SETTINGS_KEYS: &[&str] = &["rari-lsp", "rari"];
+
+/// Extension specific settings, read from `lsp.mdn-lsp.settings`, see
+/// [`lsp_settings`] for the legacy server ids.
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct MdnSettings {
+    /// Set to `false` to keep the language server out of this worktree.
+    pub enable: bool,
+    /// Keep the managed rari up to date. When `false` newer releases are only
+    /// reported.
+    pub auto_update: bool,
+    /// Per platform replacements of the downloaded rari, keyed by
+    /// [`crate::assets::platform_key`].
+    pub assets: HashMap<String, AssetOverride>,
+    /// Run rari in a dev container, see [`crate::container`].
+    pub container: Option<ContainerSettings>,
+    /// Worktree relative path to a wordlist (one word per line) extending the
+    /// bundled MDN dictionary.
+    pub dictionary: Option<String>,
+    /// Enables tools for rari contributors.
+    pub developer_mode: bool,
+    /// Absolute path of a rari to use as is in developer mode, for
+    /// extension development loops.
+    pub developer_rari: Option<String>,
+    /// Path (absolute or worktree relative) of the directory rari reads
+    /// content from, when it isn't the checkout's `files/`.
+    pub content_root: Option<String>,
+    /// Worktree relative path of BCD's `data.json`, see [`crate::compat`].
+    pub compat_data: Option<String>,
+    /// Globs of worktree relative paths the language server handles, see
+    /// [`crate::content::is_document`].
+    pub documents: Vec<String>,
+    /// Path to the mdn/content checkout holding the en-US sources of a
+    /// translated-content worktree. Defaults to the worktree itself.
+    pub en_us_content_path: Option<String>,
+    /// Local checkouts of example repositories by `owner/name`, see
+    /// [`crate::example_repos`].
+    pub example_repos: HashMap<String, String>,
+    /// Write the resolved rari to `rari-path.env`, see [`crate::export`].
+    pub export_binary_path: bool,
+    /// Allow `git fetch` of `origin/main` for the stale checkout check.
+    pub fetch_upstream: bool,
+    /// Globs of worktree relative paths that must not be edited, like
+    /// archived sections.
+    pub frozen: Vec<String>,
+    /// Directory for managed rari installs instead of the extension's work
+    /// directory, see [`crate::install_dir`].
+    pub install_dir: Option<String>,
+    /// Path of an mdn/interactive-examples checkout, absolute or relative to
+    /// the worktree, see [`crate::interactive_examples`].
+    pub interactive_examples_path: Option<String>,
+    /// Absolute path of a downloaded rari release archive to install from
+    /// instead of GitHub, see [`crate::local_archive`].
+    pub local_archive: Option<String>,
+    /// Log the language server command as a terminal line on every start,
+    /// see [`crate::command_line`].
+    pub log_command: bool,
+    /// Worktree relative path to a JSON file of additional macros, in the
+    /// format of `data/macros.json`.
+    pub macro_registry: Option<String>,
+    /// Opt in to local usage metrics, see [`crate::metrics`].
+    pub metrics: bool,
+    /// A bundle of defaults for the other settings, see [`crate::presets`].
+    pub profile: Option<Profile>,
+    /// Extra front-matter of pages from `/mdn-new-page`, with template
+    /// values, see [`crate::scaffold`].
+    pub new_page_fields: HashMap<String, String>,
+    /// Port for `/mdn-preview`, picked automatically when unset.
+    pub preview_port: Option<u16>,
+    /// Absolute path of a rari checkout, for developer mode.
+    pub rari_checkout: Option<String>,
+    /// Absolute path of a rari checkout to build and run the language server
+    /// from, see [`crate::source_build`].
+    pub rari_source_path: Option<String>,
+    /// Which roots are passed to rari with symlinks resolved, see
+    /// [`crate::paths`].
+    pub resolve_symlinks: ResolveSymlinks,
+    /// `host:port` of a running `rari lsp --listen`, used instead of a local
+    /// rari, see [`crate::remote`].
+    pub server_address: Option<String>,
+    /// How the shell environment is captured, see [`crate::shell_env`].
+    pub shell_env: ShellEnvMode,
+    /// Strip debug symbols from managed installs, see [`crate::disk::strip`].
+    pub strip_binary: bool,
+    /// Warn when the checkout is based on an upstream this many days old,
+    /// `0` disables the check.
+    pub stale_after_days: u64,
+    /// Locale to build a translation memory for, see
+    /// [`crate::translation_memory`].
+    pub translation_memory: Option<String>,
+    /// Minimum time between two lookups of the latest rari release.
+    pub update_check_interval_hours: u64,
+    /// Severity overrides for the client-side lint rules.
+    pub lint: LintConfig,
+    /// Severity overrides per locale, applied over `lint` for the pages of
+    /// that locale.
+    pub locale_lint: HashMap<String, LintConfig>,
+}
+
+/// The keys of [`MdnSettings`], to report unknown ones.
+const KEYS: &[&str] = &[
+    "enable",
+    "auto_update",
+    "assets",
+    "container",
+    "dictionary",
+    "developer_mode",
+    "developer_rari",
+    "compat_data",
+    "content_root",
+    "documents",
+    "en_us_content_path",
+    "example_repos",
+    "export_binary_path",
+    "fetch_upstream",
+    "frozen",
+    "install_dir",
+    "interactive_examples_path",
+    "local_archive",
+    "log_command",
+    "macro_registry",
+    "metrics",
+    "new_page_fields",
+    "profile",
+    "preview_port",
+    "rari_checkout",
+    "rari_source_path",
+    "resolve_symlinks",
+    "server_address",
+    "shell_env",
+    "strip_binary",
+    "stale_after_days",
+    "translation_memory",
+    "update_check_interval_hours",
+    "lint",
+    "locale_lint",
+];
+
+impl Default for MdnSettings {
+    fn default() -> Self {
+        MdnSettings {
+            enable: true,
+            auto_update: true,
+            assets: HashMap::new(),
+            container: None,
+            dictionary: None,
+            developer_mode: false,
+            developer_rari: None,
+            compat_data: None,
+            content_root: None,
+            documents: vec!["files/**/*.md".to_string()],
+            en_us_content_path: None,
+            example_repos: HashMap::new(),
+            export_binary_path: false,
+            fetch_upstream: false,
+            frozen: vec![],
+            install_dir: None,
+            interactive_examples_path: None,
+            local_archive: None,
+            log_command: false,
+            macro_registry: None,
+            metrics: false,
+            new_page_fields: HashMap::new(),
+            profile: None,
+            preview_port: None,
+            rari_checkout: None,
+            rari_source_path: None,
+            resolve_symlinks: ResolveSymlinks::default(),
+            server_address: None,
+            shell_env: ShellEnvMode::default(),
+            strip_binary: false,
+            stale_after_days: 0,
+            translation_memory: None,
+            update_check_interval_hours: 24,
+            lint: LintConfig::default(),
+            locale_lint: HashMap::new(),
+        }
+    }
+}
+
+impl MdnSettings {
+    /// The settings of `worktree`. Keys with invalid values keep their
+    /// defaults, see [`MdnSettings::problems`].
+    pub fn for_worktree(worktree: &Worktree) -> Self {
+        let Some(serde_json::Value::Object(mut settings)) = raw(worktree) else {
+            return Self::default();
+        };
+        settings.retain(|key, value| parse_key(key, value).is_ok());
+        let profile = settings
+            .get("profile")
+            .and_then(|profile| serde_json::from_value::<Profile>(profile.clone()).ok());
+        if let Some(profile) = profile {
+            settings = presets::apply(profile, settings);
+        }
+        serde_json::from_value(serde_json::Value::Object(settings)).unwrap_or_default()
+    }
+
+    /// Unknown keys and invalid values in the settings of `worktree`, which
+    /// would otherwise be ignored silently.
+    pub fn problems(worktree: &Worktree) -> Vec<String> {
+        let Some((key, lsp_settings)) = lsp_settings(worktree) else {
+            return vec![];
+        };
+        let mut problems = vec![];
+        if key != LSP_SETTINGS_KEY {
+            problems.push(format!(
+                "settings under the legacy `lsp.{key}` are deprecated, \
+                 move them to `lsp.{LSP_SETTINGS_KEY}`"
+            ));
+        }
+        let Some(settings) = lsp_settings.settings else {
+            return problems;
+        };
+        let serde_json::Value::Object(settings) = settings else {
+            problems.push(format!("`lsp.{key}.settings` must be an object"));
+            return problems;
+        };
+        for (key, value) in &settings {
+            if !KEYS.contains(&key.as_str()) {
+                problems.push(match closest_key(key) {
+                    Some(known) => format!("unknown setting `{key}`, did you mean `{known}`?"),
+                    None => format!("unknown setting `{key}`"),
+                });
+            } else if let Err(e) = parse_key(key, value) {
+                problems.push(format!("invalid setting `{key}`: {e}"));
+            } else if key == "new_page_fields" {
+                problems.extend(unknown_front_matter_keys(value));
+            }
+        }
+        problems
+    }
+
+    /// The lint severities for the page at `path`.
+    pub fn lint_config(&self, path: &str) -> LintConfig {
+        let mut config = self.lint.clone();
+        let overrides = content::locale_of(path).and_then(|locale| {
+            self.locale_lint
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case(locale))
+        });
+        if let Some((_, overrides)) = overrides {
+            config.extend(
+                overrides
+                    .iter()
+                    .map(|(rule, severity)| (rule.clone(), *severity)),
+            );
+        }
+        config
+    }
+}
+
+/// The `lsp` settings of `worktree` with the server id they are under:
+/// [`LSP_SETTINGS_KEY`], or else the first of [`LEGACY_LSP_SETTINGS_KEYS`]
+/// with anything configured.
+pub fn lsp_settings(worktree: &Worktree) -> Option<(&'static str, LspSettings)> {
+    once(LSP_SETTINGS_KEY)
+        .chain(LEGACY_LSP_SETTINGS_KEYS.iter().copied())
+        .find_map(|key| {
+            let lsp_settings = LspSettings::for_worktree(key, worktree).ok()?;
+            let configured = lsp_settings.binary.is_some()
+                || lsp_settings.initialization_options.is_some()
+                || lsp_settings.settings.is_some();
+            configured.then_some((key, lsp_settings))
+        })
+}
+
+fn raw(worktree: &Worktree) -> Option<serde_json::Value> {
+    lsp_settings(worktree).and_then(|(_, lsp_settings)| lsp_settings.settings)
+}
+
+/// Parses `key` on its own, so an error names the key it belongs to.
+/// `new_page_fields` outside of mdn/content's front-matter schema, which
+/// the `front-matter-schema` lint rule reports on every new page.
+fn unknown_front_matter_keys(fields: &serde_json::Value) -> Vec<String> {
+    let (known, _) = schemas::front_matter_keys();
+    fields
+        .as_object()
+        .into_iter()
+        .flat_map(|fields| fields.keys())
+        .filter(|field| !known.contains(field))
+        .map(|field| {
+            format!(
+                "`new_page_fields.{field}` is not in the front-matter schema of mdn/content, \
+                 pages there fail validation with it"
+            )
+        })
+        .collect()
+}
+
+fn parse_key(key: &str, value: &serde_json::Value) -> Result<MdnSettings, serde_json::Error> {
+    let mut single = serde_json::Map::new();
+    single.insert(key.to_string(), value.clone());
+    serde_json::from_value(serde_json::Value::Object(single))
+}
+
+/// The known key a typo most likely meant, like `stale_after_days` for
+/// `stale_after_day`.
+fn closest_key(key: &str) -> Option<&'static str> {
+    KEYS.iter()
+        .map(|known| (edit_distance(key, known), *known))
+        .filter(|(distance, _)| *distance <= 3)
+        .min()
+        .map(|(_, known)| known)
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, a) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, b) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(a != *b);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
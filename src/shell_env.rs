@@ -0,0 +1,84 @@
+//! The worktree's shell environment, which is slow to capture with heavy
+//! shell setups (nvm, conda).
+//!
+//! Depending on `shell_env` it is captured on every use, captured once and
+//! cached in `shell-env.json` in the work directory, or skipped. The cache
+//! only keeps the variables the extension and rari look at, so tokens and
+//! other secrets of the login environment never end up on disk.
+
+use std::{collections::HashMap, fs};
+
+use serde::Deserialize;
+use zed_extension_api::{serde_json, Worktree};
+
+use crate::settings::MdnSettings;
+
+const CACHE_FILE: &str = "shell-env.json";
+
+/// Variables kept in the cache.
+const CACHED: &[&str] = &["PATH", "HOME", "CARGO_TARGET_DIR"];
+
+/// Prefixes of variables kept in the cache: rari's own, the XDG directories
+/// and the configuration of the version managers resolving rari.
+const CACHED_PREFIXES: &[&str] = &["RARI_", "XDG_", "MISE_", "ASDF_"];
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ShellEnvMode {
+    #[default]
+    Capture,
+    Cache,
+    Skip,
+}
+
+type Cache = HashMap<String, Vec<(String, String)>>;
+
+fn load_cache() -> Cache {
+    fs::read_to_string(CACHE_FILE)
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+pub fn get(worktree: &Worktree) -> Vec<(String, String)> {
+    match MdnSettings::for_worktree(worktree).shell_env {
+        ShellEnvMode::Capture => worktree.shell_env(),
+        ShellEnvMode::Skip => vec![],
+        ShellEnvMode::Cache => {
+            let root = worktree.root_path();
+            let mut cache = load_cache();
+            // Caches written before the variables were filtered are replaced.
+            if let Some(env) = cache
+                .get(&root)
+                .filter(|env| env.iter().all(|(key, _)| is_cached(key)))
+            {
+                return env.clone();
+            }
+            let env: Vec<_> = worktree
+                .shell_env()
+                .into_iter()
+                .filter(|(key, _)| is_cached(key))
+                .collect();
+            cache.insert(root, env.clone());
+            if let Ok(json) = serde_json::to_string(&cache) {
+                fs::write(CACHE_FILE, json).ok();
+            }
+            env
+        }
+    }
+}
+
+fn is_cached(key: &str) -> bool {
+    CACHED.contains(&key) || CACHED_PREFIXES.iter().any(|prefix| key.starts_with(prefix))
+}
+
+/// Drops the cached environment of `worktree`, so the next use captures it
+/// again.
+pub fn invalidate(worktree: &Worktree) -> bool {
+    let mut cache = load_cache();
+    let removed = cache.remove(&worktree.root_path()).is_some();
+    if let Ok(json) = serde_json::to_string(&cache) {
+        fs::write(CACHE_FILE, json).ok();
+    }
+    removed
+}
@@ -0,0 +1,91 @@
+//! `rari` on the `PATH` that is a wrapper rather than the binary: npm's
+//! `node_modules/.bin` scripts with their `.cmd` variants on Windows, and
+//! shims of version managers like Volta or proto, which link to the manager.
+//!
+//! Windows can't spawn scripts, a wrapper there ends in "program not found".
+//! Wrappers are resolved to the `rari` binary next to the script they run
+//! where there is one, which also gets the status the actual path.
+
+use zed_extension_api::{self as zed, process::Command};
+
+use crate::install_dir;
+
+/// The executable to spawn for the rari at `path`. `None` for a wrapper
+/// that can't be spawned and wasn't resolved.
+pub fn resolve(path: &str, platform: zed::Os) -> Option<String> {
+    match platform {
+        zed::Os::Mac | zed::Os::Linux => {
+            // Shims and scripts run fine as they are.
+            Some(unix_target(path).unwrap_or_else(|| path.to_string()))
+        }
+        zed::Os::Windows => {
+            if path.to_lowercase().ends_with(".exe") {
+                return Some(path.to_string());
+            }
+            let resolved = npm_target(path);
+            if resolved.is_none() {
+                println!("mdn: skipping {path}, a wrapper script without a rari.exe next to what it runs");
+            }
+            resolved
+        }
+    }
+}
+
+/// The rari `path` runs: the binary it links to, or the binary next to the
+/// script it links to. `None` when it links to another binary, like a
+/// manager's shim.
+fn unix_target(path: &str) -> Option<String> {
+    let target = Command::new("realpath")
+        .arg(path)
+        .output()
+        .ok()
+        .filter(|output| output.status == Some(0))
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .unwrap_or_else(|| path.to_string());
+    if !is_script(&target) {
+        let name = target.rsplit('/').next().unwrap_or(&target);
+        return (name == "rari" || name.starts_with("rari-")).then_some(target);
+    }
+    let (dir, _) = target.rsplit_once('/')?;
+    let binary = format!("{dir}/rari");
+    (install_dir::is_file(&binary) && !is_script(&binary)).then_some(binary)
+}
+
+/// Whether the file at `path` starts with a `#!` line.
+fn is_script(path: &str) -> bool {
+    Command::new("head")
+        .args(["-c", "2", path])
+        .output()
+        .is_ok_and(|output| output.stdout == b"#!")
+}
+
+/// The `rari.exe` next to the script npm's `.cmd` wrapper for `path` runs,
+/// which names it relative to the wrapper as `"%dp0%\..\@mdn\rari\bin\rari.js"`.
+fn npm_target(path: &str) -> Option<String> {
+    let stem = path
+        .strip_suffix(".cmd")
+        .or_else(|| path.strip_suffix(".ps1"))
+        .unwrap_or(path);
+    let wrapper = format!("{stem}.cmd");
+    let output = Command::new("cmd")
+        .args(["/C", "type", &wrapper])
+        .output()
+        .ok()?;
+    let text = String::from_utf8_lossy(&output.stdout);
+    let script = text
+        .split('"')
+        .find_map(|part| part.strip_prefix("%dp0%\\"))?;
+    let (wrapper_dir, _) = wrapper.rsplit_once(['\\', '/'])?;
+    let (script_dir, _) = script.rsplit_once('\\')?;
+    let binary = format!("{wrapper_dir}\\{script_dir}\\rari.exe");
+    cmd_exists(&binary).then_some(binary)
+}
+
+/// Whether `path` exists on Windows, where there is no `test` for paths
+/// outside of the work directory.
+pub fn cmd_exists(path: &str) -> bool {
+    Command::new("cmd")
+        .args(["/C", "if", "exist", path, "echo", "yes"])
+        .output()
+        .is_ok_and(|output| String::from_utf8_lossy(&output.stdout).trim() == "yes")
+}
@@ -0,0 +1,77 @@
+//! Suggestions of a `short-title` for pages whose title is too long for the
+//! sidebar.
+
+/// Titles longer than this wrap in the sidebar.
+pub const MAX_SIDEBAR_LENGTH: usize = 40;
+
+/// Trailing words of reference titles like `Document: getElementById() method`.
+const KIND_SUFFIXES: &[&str] = &[
+    " static method",
+    " static property",
+    " instance method",
+    " instance property",
+    " method",
+    " property",
+    " event",
+    " constructor",
+    " attribute",
+    " element",
+    " header",
+];
+
+const GUIDE_PREFIXES: &[&str] = &[
+    "Using ",
+    "Introduction to ",
+    "A guide to ",
+    "An overview of ",
+];
+
+/// A `short-title` candidate for `title`, if it is too long for the sidebar.
+pub fn suggest(title: &str) -> Option<String> {
+    if title.chars().count() <= MAX_SIDEBAR_LENGTH {
+        return None;
+    }
+    let mut short = title
+        .rsplit(": ")
+        .next()
+        .unwrap_or(title)
+        .trim()
+        .to_string();
+    for suffix in KIND_SUFFIXES {
+        if let Some(stripped) = short.strip_suffix(suffix) {
+            short = stripped.to_string();
+            break;
+        }
+    }
+    for prefix in GUIDE_PREFIXES {
+        if let Some(stripped) = short.strip_prefix(prefix) {
+            short = capitalize(stripped);
+            break;
+        }
+    }
+    if let Some((before, _)) = short.split_once(" (") {
+        short = before.to_string();
+    }
+    if short.chars().count() > MAX_SIDEBAR_LENGTH {
+        let mut truncated = String::new();
+        for word in short.split_whitespace() {
+            if truncated.len() + word.len() + 1 > MAX_SIDEBAR_LENGTH {
+                break;
+            }
+            if !truncated.is_empty() {
+                truncated.push(' ');
+            }
+            truncated.push_str(word);
+        }
+        short = truncated;
+    }
+    (!short.is_empty() && short != title).then_some(short)
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
@@ -0,0 +1,43 @@
+//! Checks of `files/sidebars/*.yaml`.
+
+use zed_extension_api::Worktree;
+
+use crate::content::slug_to_file_path;
+
+/// A page a sidebar refers to.
+pub struct SidebarLink {
+    /// Zero based line.
+    pub line: usize,
+    pub slug: String,
+}
+
+/// All page links of a sidebar, from `link: /Slug` entries and plain list
+/// items like `- /Slug`.
+pub fn links(source: &str) -> Vec<SidebarLink> {
+    source
+        .lines()
+        .enumerate()
+        .filter_map(|(line, text)| {
+            let text = text.trim_start().trim_start_matches("- ").trim_start();
+            let value = text.strip_prefix("link:").unwrap_or(text).trim();
+            let value = value.trim_matches(['"', '\'']);
+            let slug = value.strip_prefix('/')?.split('#').next()?;
+            (!slug.is_empty() && !slug.contains(' ')).then(|| SidebarLink {
+                line,
+                slug: slug.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Links of the sidebar that point to pages missing from the en-US tree.
+pub fn broken_links(worktree: &Worktree, source: &str) -> Vec<SidebarLink> {
+    links(source)
+        .into_iter()
+        .filter(|link| {
+            worktree
+                .read_text_file(&slug_to_file_path("en-US", &link.slug))
+                .is_err()
+        })
+        .collect()
+}
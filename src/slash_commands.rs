@@ -0,0 +1,1350 @@
+use std::{collections::BTreeSet, env, fs};
+
+use zed_extension_api::{
+    serde_json, Result, SlashCommand, SlashCommandArgumentCompletion, SlashCommandOutput,
+    SlashCommandOutputSection, Worktree,
+};
+
+use crate::{
+    anchors, baseline, ci, compat,
+    content::{self, DocUrl},
+    example_repos, flaws, front_matter, git, glob, glossary,
+    index::ContentIndex,
+    install_dir, interactive_examples, issues, lint, macro_replace, macro_source,
+    macros::{self, Registry},
+    markup, metadata, migrate, page_types, patch, preview, processes, profile, rari, redirects,
+    references, release_notes, render_diff, scaffold, see_also,
+    settings::MdnSettings,
+    shell_env, short_title, sidebar, stats, table, titles, toolchain, translation_memory, xref,
+    MDN,
+};
+
+pub fn run(
+    mdn: &MDN,
+    command: SlashCommand,
+    args: Vec<String>,
+    worktree: Option<&Worktree>,
+) -> Result<SlashCommandOutput> {
+    let worktree = worktree.ok_or("this command needs an open worktree")?;
+    match command.name.as_str() {
+        "mdn-lint" => lint(mdn, &args, worktree),
+        "mdn-link" => link(&args, worktree),
+        "mdn-xref" => wrap_in_xref(&args, worktree),
+        "mdn-table" => format_table(&args, worktree),
+        "mdn-source-commit" => update_source_commit(&args, worktree),
+        "mdn-profile" => profile_page(mdn, &args, worktree),
+        "mdn-issues" => issues_report(mdn, worktree),
+        "mdn-preview" => preview(mdn, &args, worktree),
+        "mdn-redirects" => validate_redirects(mdn, worktree),
+        "mdn-sidebar" => validate_sidebar(&args, worktree),
+        "mdn-slug" => slug(&args, worktree),
+        "mdn-macro-source" => find_macro_source(&args, worktree),
+        "mdn-outline" => outline(&args, worktree),
+        "mdn-fragment" => fragment_links(&args, worktree),
+        "mdn-sections" => missing_sections(&args, worktree),
+        "mdn-front-matter" => sort_front_matter(&args, worktree),
+        "mdn-short-title" => add_short_title(&args, worktree),
+        "mdn-anchors" => check_anchors(&args, worktree),
+        "mdn-references" => find_references(&args, worktree),
+        "mdn-search" => search(&args, worktree),
+        "mdn-compat" => compat_status(&args, worktree),
+        "mdn-baseline" => baseline_changes(&args, worktree),
+        "mdn-fix-macros" => fix_deprecated_macros(&args, worktree),
+        "mdn-fix-markup" => fix_markup(&args, worktree),
+        "mdn-fix-headings" => fix_duplicate_headings(&args, worktree),
+        "mdn-fix-title" => fix_title(&args, worktree),
+        "mdn-page-type" => set_page_type(&args, worktree),
+        "mdn-new-page" => new_page(&args, worktree),
+        "mdn-toolchain" => share_toolchain(mdn, &args, worktree),
+        "mdn-interactive-example" => interactive_example_sources(&args, worktree),
+        "mdn-example-repos" => example_repo_files(&args, worktree),
+        "mdn-translation-memory" => translation_suggestions(&args, worktree),
+        "mdn-replace-macro" => replace_macro(&args, worktree),
+        "mdn-migrate" => migrate_front_matter(mdn, &args, worktree),
+        "mdn-glossary" => link_glossary_terms(mdn, &args, worktree),
+        "mdn-see-also" => suggest_see_also(&args, worktree),
+        "mdn-macro" => {
+            let name = args.first().ok_or("missing macro name")?;
+            let settings = MdnSettings::for_worktree(worktree);
+            let registry = Registry::for_worktree(worktree, &settings);
+            let info = registry
+                .get(name)
+                .ok_or(format!("{name} is not in the macro registry"))?;
+            let deprecated = if info.deprecated { " (deprecated)" } else { "" };
+            Ok(output(
+                format!("{}{deprecated}\n{}\n", info.signature(), info.description),
+                format!("{{{{{}}}}}", info.name),
+            ))
+        }
+        "mdn-changed" => changed_pages(&args, worktree),
+        "mdn-validate" => validate_changes(mdn, &args, worktree),
+        "mdn-render-diff" => render_diff(mdn, &args, worktree),
+        "mdn-stats" => {
+            let text = match &mdn.index {
+                Some(index) => stats::report(worktree, index),
+                None => stats::report(worktree, &ContentIndex::build(worktree)),
+            };
+            Ok(output(text, "Content statistics".to_string()))
+        }
+        "mdn-refresh-env" => {
+            let text = if shell_env::invalidate(worktree) {
+                "Cached shell environment dropped, restart the language server to use a fresh one\n"
+            } else {
+                "No cached shell environment for this worktree\n"
+            };
+            Ok(output(text.to_string(), "Shell environment".to_string()))
+        }
+        "mdn-release-notes" => {
+            let notes = release_notes::read()
+                .ok_or("no rari upgrade recorded, managed installs record their release notes")?;
+            Ok(output(
+                format!("{}\n", notes.body.trim()),
+                format!("rari {} release notes", notes.version),
+            ))
+        }
+        "mdn-explain" => {
+            if args.is_empty() {
+                let list: String = flaws::all()
+                    .iter()
+                    .map(|flaw| format!("- {}: {}\n", flaw.code, flaw.title))
+                    .collect();
+                return Ok(output(list, "rari diagnostics".to_string()));
+            }
+            let query = args.join(" ");
+            let flaw = flaws::find(&query).ok_or(format!(
+                "no explanation for \"{query}\", run /mdn-explain without arguments for the known ones"
+            ))?;
+            Ok(output(flaw.describe(), flaw.title))
+        }
+        "mdn-status" => {
+            let status = mdn
+                .status(worktree)
+                .ok_or("the MDN language server has not started in this worktree")?;
+            Ok(output(format!("{status}\n"), "rari status".to_string()))
+        }
+        "mdn-command" => {
+            let line = mdn
+                .command_line(worktree)
+                .ok_or("the MDN language server has not started in this worktree")?;
+            Ok(output(
+                format!("```sh\n{line}\n```\n"),
+                "Language server command".to_string(),
+            ))
+        }
+        name => Err(format!("unknown slash command: \"{name}\"")),
+    }
+}
+
+/// Commands taking a page path as their first argument.
+const PATH_COMMANDS: &[&str] = &[
+    "mdn-lint",
+    "mdn-table",
+    "mdn-source-commit",
+    "mdn-profile",
+    "mdn-render-diff",
+    "mdn-outline",
+    "mdn-fragment",
+    "mdn-sections",
+    "mdn-front-matter",
+    "mdn-short-title",
+    "mdn-references",
+    "mdn-fix-macros",
+    "mdn-fix-markup",
+    "mdn-fix-headings",
+    "mdn-fix-title",
+    "mdn-glossary",
+    "mdn-see-also",
+    "mdn-interactive-example",
+    "mdn-example-repos",
+    "mdn-page-type",
+];
+/// Commands taking a sidebar path as their first argument.
+const SIDEBAR_COMMANDS: &[&str] = &["mdn-sidebar"];
+const MAX_COMPLETIONS: usize = 50;
+/// Phrases listed by `/mdn-translation-memory`.
+const MAX_SUGGESTIONS: usize = 10;
+
+pub fn complete(
+    mdn: &MDN,
+    command: SlashCommand,
+    args: Vec<String>,
+) -> Vec<SlashCommandArgumentCompletion> {
+    if command.name == "mdn-explain" {
+        let query = args.join(" ").to_lowercase();
+        return flaws::all()
+            .into_iter()
+            .filter(|flaw| flaw.code.contains(&query))
+            .map(|flaw| SlashCommandArgumentCompletion {
+                label: format!("{} ({})", flaw.code, flaw.title),
+                new_text: flaw.code,
+                run_command: true,
+            })
+            .collect();
+    }
+    if command.name == "mdn-translation-memory" {
+        let Some(entries) = mdn
+            .translation_memory
+            .as_deref()
+            .and_then(translation_memory::read)
+        else {
+            return vec![];
+        };
+        return translation_memory::search(&entries, &args.join(" "))
+            .take(MAX_COMPLETIONS)
+            .map(|entry| SlashCommandArgumentCompletion {
+                label: match entry.translations.first() {
+                    Some((translation, _)) => format!("{} → {translation}", entry.source),
+                    None => entry.source.clone(),
+                },
+                new_text: entry.source.clone(),
+                run_command: true,
+            })
+            .collect();
+    }
+    if matches!(command.name.as_str(), "mdn-page-type" | "mdn-new-page") && args.len() == 2 {
+        let slug = match command.name.as_str() {
+            "mdn-page-type" => page_types::slug_of_path(&args[0]),
+            _ => Some(args[0].clone()),
+        };
+        let Some(area) = slug.and_then(|slug| page_types::area(&slug)) else {
+            return vec![];
+        };
+        return area
+            .page_types
+            .into_iter()
+            .filter(|page_type| page_type.contains(&args[1]))
+            .map(|page_type| SlashCommandArgumentCompletion {
+                label: page_type.clone(),
+                new_text: page_type,
+                run_command: true,
+            })
+            .collect();
+    }
+    let Some(index) = &mdn.index else {
+        return vec![];
+    };
+    if args.len() > 1 {
+        return vec![];
+    }
+    let query = args
+        .first()
+        .map(|arg| arg.to_lowercase())
+        .unwrap_or_default();
+    let candidates: Box<dyn Iterator<Item = &str>> = match command.name.as_str() {
+        name if PATH_COMMANDS.contains(&name) => Box::new(index.files.iter().map(String::as_str)),
+        name if SIDEBAR_COMMANDS.contains(&name) => {
+            Box::new(index.sidebars.iter().map(String::as_str))
+        }
+        "mdn-slug" => Box::new(index.en_us_slugs()),
+        "mdn-macro" => match &mdn.registry {
+            Some(registry) => Box::new(registry.names()),
+            None => return vec![],
+        },
+        _ => return vec![],
+    };
+    candidates
+        .filter(|candidate| candidate.contains(&query))
+        .take(MAX_COMPLETIONS)
+        .map(|candidate| SlashCommandArgumentCompletion {
+            label: candidate.to_string(),
+            new_text: candidate.to_string(),
+            run_command: command.name != "mdn-table",
+        })
+        .collect()
+}
+
+fn lint(mdn: &MDN, args: &[String], worktree: &Worktree) -> Result<SlashCommandOutput> {
+    let path = args.first().ok_or("missing path to lint")?;
+    let settings = MdnSettings::for_worktree(worktree);
+    if !content::is_document(&settings, path) {
+        return Err(format!("{path} is not an MDN document (see `documents`)"));
+    }
+    let source = worktree.read_text_file(path)?;
+    let registry = Registry::for_worktree(worktree, &settings);
+    let pages = metadata::load(worktree).ok();
+    let document = lint::Document {
+        source: &source,
+        registry: &registry,
+        index: mdn.index.as_ref(),
+        locale: content::locale_of(path),
+        pages: pages.as_deref(),
+        example_repos: &example_repos::checkouts(worktree, &settings),
+    };
+    let mut config = settings.lint_config(path);
+    if mdn.rari_unavailable(worktree) {
+        // Stand in for rari's link checks.
+        config
+            .entry("broken-link".to_string())
+            .or_insert(lint::Severity::Warning);
+    }
+    let diagnostics = lint::lint(&document, &config);
+
+    let mut text = String::new();
+    if glob::matches_any(&settings.frozen, path) {
+        text.push_str(&format!(
+            "{path}:1:1: error [frozen] this page is frozen and must not be edited\n"
+        ));
+    } else if diagnostics.is_empty() {
+        text.push_str(&format!("{path}: no issues\n"));
+    }
+    for diagnostic in &diagnostics {
+        text.push_str(&format!("{path}:{diagnostic}\n"));
+    }
+    // Zed shows the label on the collapsed section, an at-a-glance count
+    // for the page.
+    Ok(output(
+        text,
+        format!("Lint {path} · {}", lint::summary(&diagnostics)),
+    ))
+}
+
+fn link(args: &[String], worktree: &Worktree) -> Result<SlashCommandOutput> {
+    let url = args.first().ok_or("missing URL")?;
+    let url = DocUrl::parse(url).ok_or(format!("not an MDN document URL: {url}"))?;
+    let front_matter = content::read_front_matter(worktree, &url)?;
+    let title = front_matter.title().unwrap_or_else(|| url.slug.clone());
+
+    let mut text = format!("[{title}]({})\n", url.relative());
+    if let Some(xref) = xref::from_url(&url, front_matter.page_type().as_deref()) {
+        text.push_str(&xref);
+        text.push('\n');
+    }
+    Ok(output(text, format!("Link to {}", url.slug)))
+}
+
+fn wrap_in_xref(args: &[String], worktree: &Worktree) -> Result<SlashCommandOutput> {
+    let name = args.join(" ");
+    if name.is_empty() {
+        return Err("missing API name".to_string());
+    }
+    let text =
+        xref::for_name(worktree, &name).ok_or(format!("no reference page found for \"{name}\""))?;
+    Ok(output(text, format!("Cross reference to {name}")))
+}
+
+fn format_table(args: &[String], worktree: &Worktree) -> Result<SlashCommandOutput> {
+    let [path, line] = args else {
+        return Err("usage: /mdn-table <path> <line>".to_string());
+    };
+    let line: usize = line
+        .parse()
+        .map_err(|_| format!("invalid line number: {line}"))?;
+    let source = worktree.read_text_file(path)?;
+    let (lines, table) = table::format_at(&source, line.saturating_sub(1))
+        .ok_or(format!("no table at {path}:{line}"))?;
+    Ok(output(
+        format!("{table}\n"),
+        format!("Table {path}:{}-{}", lines.start + 1, lines.end),
+    ))
+}
+
+fn update_source_commit(args: &[String], worktree: &Worktree) -> Result<SlashCommandOutput> {
+    let path = args.first().ok_or("missing path of a translated page")?;
+    let source_path =
+        content::en_us_counterpart(path).ok_or(format!("{path} is not a translated page"))?;
+    let settings = MdnSettings::for_worktree(worktree);
+    let repo = content::en_us_repo(worktree, &settings);
+    let commit = git::last_commit(&repo, &source_path)?;
+
+    let source = worktree.read_text_file(path)?;
+    let updated = front_matter::set_nested_value(&source, "l10n", "sourceCommit", &commit)
+        .ok_or(format!("{path} has no front-matter"))?;
+    let block = front_matter::block(&updated).unwrap_or(&updated);
+    Ok(output(
+        format!("{block}\n"),
+        format!("l10n.sourceCommit for {path}"),
+    ))
+}
+
+fn profile_page(mdn: &MDN, args: &[String], worktree: &Worktree) -> Result<SlashCommandOutput> {
+    let path = args.first().ok_or("missing path of the page to profile")?;
+    let binary = mdn.installed_rari(worktree)?;
+    let file = format!("{}/{path}", worktree.root_path());
+    let log = rari::run(
+        &binary,
+        worktree,
+        &["build", "--files", &file],
+        &[("RUST_LOG", "rari_doc::templ=trace")],
+    )?;
+    let timings = profile::macro_timings(&log);
+    if timings.is_empty() {
+        return Err(format!(
+            "no timings in rari output for {path}, profiling needs a rari logging span close events"
+        ));
+    }
+
+    let mut text = format!(
+        "{:<32} {:>6} {:>12} {:>10}\n",
+        "macro", "calls", "total ms", "max ms"
+    );
+    for (name, timing) in timings {
+        text.push_str(&format!(
+            "{name:<32} {:>6} {:>12.2} {:>10.2}\n",
+            timing.calls, timing.total_ms, timing.max_ms
+        ));
+    }
+    Ok(output(text, format!("Profile {path}")))
+}
+
+fn issues_report(mdn: &MDN, worktree: &Worktree) -> Result<SlashCommandOutput> {
+    const REPORT_FILE: &str = "issues.json";
+    let binary = mdn.installed_rari(worktree)?;
+    let report_path = env::current_dir()
+        .map_err(|e| format!("failed to locate work directory: {e}"))?
+        .join(REPORT_FILE);
+    rari::run(
+        &binary,
+        worktree,
+        &["build", "--issues", &report_path.to_string_lossy()],
+        &[],
+    )?;
+    let report = fs::read_to_string(REPORT_FILE)
+        .map_err(|e| format!("rari did not write an issues report: {e}"))?;
+    let report: serde_json::Value =
+        serde_json::from_str(&report).map_err(|e| format!("invalid issues report: {e}"))?;
+
+    let root = format!("{}/", worktree.root_path());
+    let issues = issues::parse(&report);
+    let mut text = format!("{} issues\n", issues.len());
+    for issue in issues {
+        let file = issue.file.strip_prefix(&root).unwrap_or(&issue.file);
+        text.push_str(&format!(
+            "{file}:{}:{}: {}\n",
+            issue.line, issue.column, issue.message
+        ));
+    }
+    Ok(output(text, "Content issues".to_string()))
+}
+
+fn preview(mdn: &MDN, args: &[String], worktree: &Worktree) -> Result<SlashCommandOutput> {
+    const LABEL: &str = processes::PREVIEW;
+    let text = match args.first().map(String::as_str).unwrap_or("start") {
+        "start" => {
+            let settings = MdnSettings::for_worktree(worktree);
+            match preview::select(worktree.id(), settings.preview_port)? {
+                preview::Port::Reuse(port) => {
+                    format!("Preview server already running at http://localhost:{port}\n")
+                }
+                preview::Port::Free(port) => {
+                    let binary = mdn.installed_rari(worktree)?;
+                    let pid = processes::spawn(
+                        worktree.id(),
+                        LABEL,
+                        Some(port),
+                        &binary,
+                        &["serve", "--port", &port.to_string()],
+                        &rari::environment(worktree),
+                    )?;
+                    format!("Preview server started at http://localhost:{port} (pid {pid})\n")
+                }
+            }
+        }
+        "stop" => match processes::stop(worktree.id(), LABEL) {
+            true => "Preview server stopped\n".to_string(),
+            false => "No preview server running\n".to_string(),
+        },
+        "status" => match processes::running(worktree.id(), LABEL) {
+            Some(process) => format!(
+                "Preview server running at http://localhost:{} (pid {})\n",
+                process.port.unwrap_or(preview::DEFAULT_PORT),
+                process.pid
+            ),
+            None => "No preview server running\n".to_string(),
+        },
+        action => {
+            return Err(format!(
+                "unknown action \"{action}\", use start, stop or status"
+            ))
+        }
+    };
+    Ok(output(text, "Preview server".to_string()))
+}
+
+fn validate_redirects(mdn: &MDN, worktree: &Worktree) -> Result<SlashCommandOutput> {
+    let binary = mdn.installed_rari(worktree)?;
+    let (valid, text) = rari::output(&binary, worktree, &["content", "validate-redirects"], &[])?;
+    let text = match (valid, text.trim().is_empty()) {
+        (true, true) => "Redirects are valid\n".to_string(),
+        _ => redirects::locate(worktree, &text),
+    };
+    Ok(output(text, "Redirect validation".to_string()))
+}
+
+/// The properly cased slug of a page, as sidebars and links want it.
+fn slug(args: &[String], worktree: &Worktree) -> Result<SlashCommandOutput> {
+    let query = args.first().ok_or("missing slug")?;
+    let url = DocUrl {
+        locale: "en-US".to_string(),
+        slug: query.trim_start_matches('/').to_string(),
+        fragment: None,
+    };
+    let slug = content::read_front_matter(worktree, &url)?
+        .value("slug")
+        .unwrap_or(url.slug);
+    Ok(output(format!("/{slug}\n"), "Slug".to_string()))
+}
+
+/// The headings of a page, grouped by top level section and labeled with
+/// the page type's template, followed by missing required sections.
+fn outline(args: &[String], worktree: &Worktree) -> Result<SlashCommandOutput> {
+    let path = args.first().ok_or("missing path of the page")?;
+    let source = worktree.read_text_file(path)?;
+    let page_type = front_matter::FrontMatter::parse(&source).and_then(|fm| fm.page_type());
+    let template = page_type.as_deref().and_then(page_types::template);
+
+    let mut text = format!(
+        "{path} ({})\n",
+        page_type.as_deref().unwrap_or("no page-type")
+    );
+    let mut sections = vec![];
+    let mut group: Option<(usize, String)> = None;
+    for (heading, anchor) in anchors::page_anchors(&source) {
+        if heading.level <= 2 {
+            if let Some((start, label)) = group.take() {
+                sections.push(SlashCommandOutputSection {
+                    range: (start..text.len()).into(),
+                    label,
+                });
+            }
+            group = Some((text.len(), heading.text.clone()));
+        }
+        let note = match template.as_ref().map(|t| t.section(&heading.text)) {
+            Some(Some(section)) if section.level != heading.level => {
+                format!(" (expected as {})", "#".repeat(section.level))
+            }
+            Some(None) if heading.level == 2 => " (not in the template)".to_string(),
+            _ => String::new(),
+        };
+        text.push_str(&format!(
+            "{path}:{}: {} {} #{anchor}{note}\n",
+            heading.line + 1,
+            "#".repeat(heading.level),
+            heading.text
+        ));
+    }
+    if let Some((start, label)) = group {
+        sections.push(SlashCommandOutputSection {
+            range: (start..text.len()).into(),
+            label,
+        });
+    }
+    if let Some(template) = &template {
+        for section in template.missing(&source) {
+            text.push_str(&format!("missing: {}\n", section.heading));
+        }
+    }
+    sections.insert(
+        0,
+        SlashCommandOutputSection {
+            range: (0..text.len()).into(),
+            label: format!("Outline of {path}"),
+        },
+    );
+    Ok(SlashCommandOutput { sections, text })
+}
+
+/// Same-page links to the headings and live samples of a page, optionally
+/// only those whose id contains the second argument.
+fn fragment_links(args: &[String], worktree: &Worktree) -> Result<SlashCommandOutput> {
+    let path = args.first().ok_or("missing path of the page")?;
+    let query = args
+        .get(1)
+        .map(|arg| arg.to_lowercase())
+        .unwrap_or_default();
+    let source = worktree.read_text_file(path)?;
+    let mut text = String::new();
+    let mut ids = vec![];
+    for (heading, id) in anchors::page_anchors(&source) {
+        if id.contains(&query) {
+            text.push_str(&format!("[{}](#{id})\n", heading.text));
+        }
+        ids.push(id);
+    }
+    for id in anchors::live_sample_ids(&source) {
+        if id.contains(&query) && !ids.contains(&id) {
+            text.push_str(&format!("[live sample](#{id})\n"));
+        }
+    }
+    if text.is_empty() {
+        text.push_str("No matching headings\n");
+    }
+    Ok(output(text, format!("Fragments of {path}")))
+}
+
+/// Skeletons of the required sections a page lacks, one output section per
+/// skeleton, labeled with where it goes.
+fn missing_sections(args: &[String], worktree: &Worktree) -> Result<SlashCommandOutput> {
+    let path = args.first().ok_or("missing path of the page")?;
+    let source = worktree.read_text_file(path)?;
+    let page_type = front_matter::FrontMatter::parse(&source)
+        .and_then(|fm| fm.page_type())
+        .ok_or(format!("{path} has no page-type"))?;
+    let template =
+        page_types::template(&page_type).ok_or(format!("no template for {page_type} pages"))?;
+    let missing = template.missing(&source);
+    if missing.is_empty() {
+        return Ok(output(
+            format!("{path} has all sections required for {page_type} pages\n"),
+            format!("Sections of {path}"),
+        ));
+    }
+    let mut text = String::new();
+    let mut sections = vec![];
+    for section in missing {
+        let start = text.len();
+        let line = template.insertion_line(section, &source);
+        text.push_str(&section.skeleton());
+        sections.push(SlashCommandOutputSection {
+            range: (start..text.len()).into(),
+            label: format!("{} (insert at {path}:{})", section.heading, line + 1),
+        });
+    }
+    Ok(SlashCommandOutput { sections, text })
+}
+
+/// The front-matter of a page with keys in canonical order.
+fn sort_front_matter(args: &[String], worktree: &Worktree) -> Result<SlashCommandOutput> {
+    let path = args.first().ok_or("missing path of the page")?;
+    let source = worktree.read_text_file(path)?;
+    let front_matter =
+        front_matter::FrontMatter::parse(&source).ok_or(format!("{path} has no front-matter"))?;
+    Ok(output(
+        format!("{}\n", front_matter.to_sorted_block()),
+        format!("Front-matter of {path}"),
+    ))
+}
+
+/// The page types legal where the page is, or its front-matter with
+/// `page-type` set to the second argument.
+fn set_page_type(args: &[String], worktree: &Worktree) -> Result<SlashCommandOutput> {
+    let path = args.first().ok_or("missing path of the page")?;
+    let source = worktree.read_text_file(path)?;
+    let mut front_matter =
+        front_matter::FrontMatter::parse(&source).ok_or(format!("{path} has no front-matter"))?;
+    let area = page_types::slug_of_path(path).and_then(|slug| page_types::area(&slug));
+    let Some(page_type) = args.get(1) else {
+        let text = match &area {
+            Some(area) => area
+                .page_types
+                .iter()
+                .map(|page_type| format!("- {page_type}\n"))
+                .collect(),
+            None => format!("{path} isn't in an area with a fixed set of page types\n"),
+        };
+        return Ok(output(text, format!("Page types for {path}")));
+    };
+    if let Some(area) = area.filter(|area| !area.page_types.contains(page_type)) {
+        return Err(format!(
+            "{page_type} isn't a page type for pages below {}, use one of {}",
+            area.prefix,
+            area.page_types.join(", ")
+        ));
+    }
+    front_matter.set("page-type", page_type);
+    Ok(output(
+        format!("{}\n", front_matter.to_block()),
+        format!("page-type of {path}"),
+    ))
+}
+
+/// A new page at the slug in the first argument, of the page type in the
+/// second.
+fn new_page(args: &[String], worktree: &Worktree) -> Result<SlashCommandOutput> {
+    let slug = args.first().ok_or("missing slug of the new page")?;
+    let page_type = args.get(1);
+    let path = content::slug_to_file_path("en-US", slug.trim_matches('/'));
+    if worktree.read_text_file(&path).is_ok() {
+        return Err(format!("{path} already exists"));
+    }
+    if let Some(page_type) = page_type {
+        if let Some(area) =
+            page_types::area(slug).filter(|area| !area.page_types.contains(page_type))
+        {
+            return Err(format!(
+                "{page_type} isn't a page type for pages below {}, use one of {}",
+                area.prefix,
+                area.page_types.join(", ")
+            ));
+        }
+    }
+    let settings = MdnSettings::for_worktree(worktree);
+    let identity = scaffold::Identity::of(&content::repo_root(worktree));
+    let text = scaffold::page(
+        slug,
+        page_type.map(String::as_str),
+        &settings.new_page_fields,
+        &identity,
+    )?;
+    Ok(output(text, format!("New page at {path}")))
+}
+
+/// The front-matter of a page with a suggested `short-title` added.
+fn add_short_title(args: &[String], worktree: &Worktree) -> Result<SlashCommandOutput> {
+    let path = args.first().ok_or("missing path of the page")?;
+    let source = worktree.read_text_file(path)?;
+    let title = front_matter::FrontMatter::parse(&source)
+        .and_then(|fm| fm.title())
+        .ok_or(format!("{path} has no title"))?;
+    let suggestion = short_title::suggest(&title).ok_or(format!(
+        "the title of {path} fits the sidebar, no short-title needed"
+    ))?;
+    let updated = front_matter::insert_after(&source, "title", "short-title", &suggestion)
+        .ok_or(format!("{path} has no front-matter"))?;
+    let block = front_matter::block(&updated).unwrap_or(&updated);
+    Ok(output(
+        format!("{block}\n"),
+        format!("short-title for {path}"),
+    ))
+}
+
+/// Pages documenting features whose Baseline status changed since the
+/// changes were last accepted.
+fn baseline_changes(args: &[String], worktree: &Worktree) -> Result<SlashCommandOutput> {
+    if args.first().map(String::as_str) == Some("accept") {
+        baseline::accept(worktree)?;
+        return Ok(output(
+            "Baseline changes accepted\n".to_string(),
+            "Baseline".to_string(),
+        ));
+    }
+    let mut text = String::new();
+    for change in baseline::changes(worktree)? {
+        for page in baseline::pages_for(worktree, &change.key) {
+            text.push_str(&format!(
+                "{page}: warning [baseline-changed] {} went from {} to {}, check status macros and prose\n",
+                change.key, change.before, change.after
+            ));
+        }
+    }
+    if text.is_empty() {
+        text.push_str("No documented feature changed its Baseline status\n");
+    } else {
+        text.push_str("Run `/mdn-baseline accept` once the pages are updated\n");
+    }
+    Ok(output(text, "Baseline changes".to_string()))
+}
+
+/// The lines of a page with deprecated macros rewritten, one section per
+/// line to replace.
+fn fix_deprecated_macros(args: &[String], worktree: &Worktree) -> Result<SlashCommandOutput> {
+    let path = args.first().ok_or("missing path of the page")?;
+    let source = worktree.read_text_file(path)?;
+    let settings = MdnSettings::for_worktree(worktree);
+    let registry = Registry::for_worktree(worktree, &settings);
+    let rewritten = macros::rewrite_deprecated(&source, &registry);
+    if rewritten.is_empty() {
+        return Ok(output(
+            format!("{path} has no deprecated macros with a replacement\n"),
+            format!("Macros of {path}"),
+        ));
+    }
+    let mut text = String::new();
+    let mut sections = vec![];
+    for (line, new_text) in rewritten {
+        let start = text.len();
+        text.push_str(&new_text);
+        text.push('\n');
+        sections.push(SlashCommandOutputSection {
+            range: (start..text.len()).into(),
+            label: format!("{path}:{}", line + 1),
+        });
+    }
+    Ok(SlashCommandOutput { sections, text })
+}
+
+/// The lines of a page with the first use of each glossary term wrapped in
+/// `{{Glossary}}`.
+fn link_glossary_terms(
+    mdn: &MDN,
+    args: &[String],
+    worktree: &Worktree,
+) -> Result<SlashCommandOutput> {
+    let path = args.first().ok_or("missing path of the page")?;
+    let source = worktree.read_text_file(path)?;
+    let terms = match &mdn.index {
+        Some(index) => glossary::terms(index),
+        None => glossary::terms(&ContentIndex::build(worktree)),
+    };
+    let suggestions = glossary::suggest(&source, &terms);
+    if suggestions.is_empty() {
+        return Ok(output(
+            format!("{path} has no unlinked glossary terms\n"),
+            format!("Glossary terms of {path}"),
+        ));
+    }
+    let mut text = String::new();
+    let mut sections = vec![];
+    for (line, new_text) in glossary::rewrite(&source, &suggestions) {
+        let start = text.len();
+        text.push_str(&new_text);
+        text.push('\n');
+        sections.push(SlashCommandOutputSection {
+            range: (start..text.len()).into(),
+            label: format!("{path}:{}", line + 1),
+        });
+    }
+    Ok(SlashCommandOutput { sections, text })
+}
+
+/// Related pages to add to the "See also" section, as list items grouped
+/// by how they relate.
+fn suggest_see_also(args: &[String], worktree: &Worktree) -> Result<SlashCommandOutput> {
+    let path = args.first().ok_or("missing path of the page")?;
+    let pages = metadata::load(worktree)?;
+    let suggestions = see_also::suggest(worktree, path, &pages)?;
+    if suggestions.is_empty() {
+        return Ok(output(
+            format!("No related pages that {path} doesn't link yet\n"),
+            format!("See also for {path}"),
+        ));
+    }
+    let mut text = String::new();
+    let mut sections = vec![];
+    let mut reasons: Vec<&str> = suggestions.iter().map(|s| s.reason).collect();
+    reasons.dedup();
+    for reason in reasons {
+        let start = text.len();
+        for suggestion in suggestions.iter().filter(|s| s.reason == reason) {
+            text.push_str(&suggestion.list_item());
+            text.push('\n');
+        }
+        sections.push(SlashCommandOutputSection {
+            range: (start..text.len()).into(),
+            label: format!("See also: {reason}"),
+        });
+    }
+    Ok(SlashCommandOutput { sections, text })
+}
+
+/// Front-matter changes on all documents matching a glob:
+/// `glob transform… [--apply]`. Only writes to the worktree with `--apply`.
+fn migrate_front_matter(
+    mdn: &MDN,
+    args: &[String],
+    worktree: &Worktree,
+) -> Result<SlashCommandOutput> {
+    let apply = args.iter().any(|arg| arg == "--apply");
+    let args: Vec<&str> = args
+        .iter()
+        .map(String::as_str)
+        .filter(|arg| *arg != "--apply")
+        .collect();
+    let (pattern, transform) = args
+        .split_first()
+        .ok_or("missing glob of the pages, like files/en-us/web/api/**")?;
+    let transform = migrate::Transform::parse(transform)?;
+    let hunks = match &mdn.index {
+        Some(index) => migrate::plan(worktree, index, pattern, &transform),
+        None => migrate::plan(
+            worktree,
+            &ContentIndex::build(worktree),
+            pattern,
+            &transform,
+        ),
+    };
+    let mut text = if apply {
+        patch::apply(worktree, &hunks)?;
+        format!("Changed {} pages\n", hunks.len())
+    } else {
+        format!(
+            "{} pages would change, run again with --apply to write them\n",
+            hunks.len()
+        )
+    };
+    text.push_str(&patch::to_patch(&hunks));
+    Ok(output(text, format!("Front-matter of {pattern}")))
+}
+
+/// Calls matching a pattern across the content tree, rewritten with an
+/// optional template: `pattern [=> template] [--apply]`. Only writes to the
+/// worktree with `--apply`.
+fn replace_macro(args: &[String], worktree: &Worktree) -> Result<SlashCommandOutput> {
+    let apply = args.iter().any(|arg| arg == "--apply");
+    let args: Vec<&str> = args
+        .iter()
+        .map(String::as_str)
+        .filter(|arg| *arg != "--apply")
+        .collect();
+    let joined = args.join(" ");
+    let (pattern, template) = match joined.split_once("=>") {
+        Some((pattern, template)) => (pattern, Some(template.trim())),
+        None => (joined.as_str(), None),
+    };
+    if apply && template.is_none() {
+        return Err("--apply needs a replacement: pattern => template".to_string());
+    }
+    let label = format!("Replace {}", pattern.trim());
+    let pattern = macro_replace::Pattern::parse(pattern)?;
+    let edits = macro_replace::find(worktree, &pattern, template)?;
+    let files = edits
+        .iter()
+        .map(|edit| &edit.path)
+        .collect::<BTreeSet<_>>()
+        .len();
+    let mut text = if apply {
+        macro_replace::apply(worktree, &edits)?;
+        format!("Rewrote {} lines in {files} files\n", edits.len())
+    } else {
+        format!("{} lines in {files} files\n", edits.len())
+    };
+    for edit in &edits {
+        text.push_str(&format!("{}:{}:\n", edit.path, edit.line + 1));
+        if template.is_some() {
+            text.push_str(&format!("- {}\n+ {}\n", edit.before, edit.after));
+        } else {
+            text.push_str(&format!("  {}\n", edit.before));
+        }
+    }
+    Ok(output(text, label))
+}
+
+/// The lines of a page with keyboard shortcuts, HTTP statuses and the like
+/// wrapped in their markup, see [`markup`].
+fn fix_markup(args: &[String], worktree: &Worktree) -> Result<SlashCommandOutput> {
+    let path = args.first().ok_or("missing path of the page")?;
+    let source = worktree.read_text_file(path)?;
+    let rules = markup::rules();
+    let matches = markup::find(&source, &rules);
+    if matches.is_empty() {
+        return Ok(output(
+            format!("{path} has no text that should be markup\n"),
+            format!("Markup of {path}"),
+        ));
+    }
+    let mut text = String::new();
+    let mut sections = vec![];
+    for (line, new_text) in markup::rewrite(&source, &matches) {
+        let start = text.len();
+        text.push_str(&new_text);
+        text.push('\n');
+        sections.push(SlashCommandOutputSection {
+            range: (start..text.len()).into(),
+            label: format!("{path}:{}", line + 1),
+        });
+    }
+    Ok(SlashCommandOutput { sections, text })
+}
+
+fn fix_duplicate_headings(args: &[String], worktree: &Worktree) -> Result<SlashCommandOutput> {
+    let path = args.first().ok_or("missing path of the page")?;
+    let source = worktree.read_text_file(path)?;
+    let duplicates = anchors::duplicate_headings(&source);
+    if duplicates.is_empty() {
+        return Ok(output(
+            format!("{path} has no duplicate headings\n"),
+            format!("Headings of {path}"),
+        ));
+    }
+    let mut text = String::new();
+    let mut sections = vec![];
+    for duplicate in &duplicates {
+        let Some(rewritten) = duplicate.rewrite(&source) else {
+            continue;
+        };
+        let start = text.len();
+        text.push_str(&rewritten);
+        text.push('\n');
+        sections.push(SlashCommandOutputSection {
+            range: (start..text.len()).into(),
+            label: format!(
+                "{path}:{} (was #{})",
+                duplicate.heading.line + 1,
+                duplicate.id
+            ),
+        });
+    }
+    Ok(SlashCommandOutput { sections, text })
+}
+
+fn interactive_example_sources(args: &[String], worktree: &Worktree) -> Result<SlashCommandOutput> {
+    let path = args.first().ok_or("missing path of the page")?;
+    let settings = MdnSettings::for_worktree(worktree);
+    let root = interactive_examples::root(worktree, &settings).ok_or(
+        "no interactive-examples checkout found next to the content, \
+         set `interactive_examples_path`",
+    )?;
+    let source = worktree.read_text_file(path)?;
+    let examples = interactive_examples::examples(&root, &source);
+    if examples.is_empty() {
+        return Ok(output(
+            format!("{path} embeds no interactive examples\n"),
+            format!("Interactive examples of {path}"),
+        ));
+    }
+    let mut text = String::new();
+    let mut sections = vec![];
+    for example in &examples {
+        let start = text.len();
+        if example.files.is_empty() {
+            text.push_str("not found in the interactive-examples checkout\n");
+        }
+        for file in &example.files {
+            text.push_str(file);
+            text.push('\n');
+        }
+        sections.push(SlashCommandOutputSection {
+            range: (start..text.len()).into(),
+            label: format!("{path}:{} {}", example.line + 1, example.key),
+        });
+    }
+    Ok(SlashCommandOutput { sections, text })
+}
+
+fn example_repo_files(args: &[String], worktree: &Worktree) -> Result<SlashCommandOutput> {
+    let path = args.first().ok_or("missing path of the page")?;
+    let settings = MdnSettings::for_worktree(worktree);
+    let checkouts = example_repos::checkouts(worktree, &settings);
+    if checkouts.is_empty() {
+        return Err("no checkouts configured in `example_repos`".to_string());
+    }
+    let source = worktree.read_text_file(path)?;
+    let mut text = String::new();
+    let mut sections = vec![];
+    for reference in example_repos::references(&source) {
+        let Some(target) = example_repos::resolve(&checkouts, &reference) else {
+            continue;
+        };
+        let start = text.len();
+        text.push_str(&match target {
+            example_repos::Target::File(file) => format!("{file}\n"),
+            example_repos::Target::Missing(file) => format!("{file} (missing)\n"),
+            example_repos::Target::NoCheckout(checkout) => {
+                format!("{checkout} (checkout missing)\n")
+            }
+        });
+        sections.push(SlashCommandOutputSection {
+            range: (start..text.len()).into(),
+            label: format!("{path}:{} {}", reference.line + 1, reference.repo),
+        });
+    }
+    if sections.is_empty() {
+        return Ok(output(
+            format!("{path} links to none of the repositories in `example_repos`\n"),
+            format!("Example files of {path}"),
+        ));
+    }
+    Ok(SlashCommandOutput { sections, text })
+}
+
+fn translation_suggestions(args: &[String], worktree: &Worktree) -> Result<SlashCommandOutput> {
+    let query = args.join(" ");
+    if query.is_empty() {
+        return Err("missing phrase to look up".to_string());
+    }
+    let settings = MdnSettings::for_worktree(worktree);
+    let locale = settings
+        .translation_memory
+        .as_deref()
+        .ok_or("set `translation_memory` to the locale to build a translation memory for")?;
+    let file = translation_memory::file(worktree)?;
+    translation_memory::finish(&file);
+    let entries = translation_memory::read(&file)
+        .ok_or("the translation memory is still being built, try again in a minute")?;
+    let mut text = String::new();
+    let mut sections = vec![];
+    for entry in translation_memory::search(&entries, &query).take(MAX_SUGGESTIONS) {
+        let start = text.len();
+        for (translation, pages) in &entry.translations {
+            let plural = if *pages == 1 { "" } else { "s" };
+            text.push_str(&format!("{translation} ({pages} page{plural})\n"));
+        }
+        sections.push(SlashCommandOutputSection {
+            range: (start..text.len()).into(),
+            label: entry.source.clone(),
+        });
+    }
+    if sections.is_empty() {
+        return Ok(output(
+            format!("no recurring phrase containing \"{query}\" in the {locale} translations\n"),
+            format!("Translations of {query}"),
+        ));
+    }
+    Ok(SlashCommandOutput { sections, text })
+}
+
+fn fix_title(args: &[String], worktree: &Worktree) -> Result<SlashCommandOutput> {
+    let path = args.first().ok_or("missing path of the page")?;
+    let source = worktree.read_text_file(path)?;
+    let Some((end, text)) = titles::fix(&source) else {
+        return Ok(output(
+            format!("{path} has no level 1 headings and a conventional title\n"),
+            format!("Title of {path}"),
+        ));
+    };
+    Ok(output(text, format!("{path}:1-{end}")))
+}
+
+fn share_toolchain(mdn: &MDN, args: &[String], worktree: &Worktree) -> Result<SlashCommandOutput> {
+    let [action, dir] = args else {
+        return Err("usage: /mdn-toolchain export|import <directory>".to_string());
+    };
+    let dir = install_dir::expand_home(dir.trim_end_matches('/'), &worktree.shell_env())
+        .filter(|dir| dir.starts_with('/'))
+        .ok_or(format!("{dir} is not an absolute path"))?;
+    match action.as_str() {
+        "export" => {
+            let manifest = toolchain::export(&mdn.installed_rari(worktree)?, &dir)?;
+            Ok(output(
+                format!(
+                    "Wrote {dir}/{} and its manifest, share the directory and run \
+                     `/mdn-toolchain import <directory>` on the other machines\n",
+                    manifest.archive
+                ),
+                format!("rari {} toolchain", manifest.version),
+            ))
+        }
+        "import" => {
+            let (manifest, archive) = toolchain::import(&dir)?;
+            Ok(output(
+                format!(
+                    "Add to `lsp.mdn-lsp.settings` to install rari {} from the bundle \
+                     and restart the language server:\n\n{}\n",
+                    manifest.version,
+                    toolchain::settings(&manifest, &archive)
+                ),
+                format!("rari {} toolchain", manifest.version),
+            ))
+        }
+        action => Err(format!("unknown action {action}, use export or import")),
+    }
+}
+
+/// Browser support of a symbol from prose, in place of a hover.
+fn compat_status(args: &[String], worktree: &Worktree) -> Result<SlashCommandOutput> {
+    let symbol = args.join(" ");
+    if symbol.is_empty() {
+        return Err("missing symbol, like fetch() or scroll-snap-type".to_string());
+    }
+    let data = compat::load(worktree)?;
+    let (key, compat) =
+        compat::resolve(&data, &symbol).ok_or(format!("no compat data for {symbol}"))?;
+    Ok(output(
+        compat::describe(&key, compat),
+        format!("Compat of {symbol}"),
+    ))
+}
+
+/// Pages whose title or slug contains all words of the query.
+fn search(args: &[String], worktree: &Worktree) -> Result<SlashCommandOutput> {
+    if args.is_empty() {
+        return Err("missing search terms".to_string());
+    }
+    let words: Vec<String> = args.iter().map(|word| word.to_lowercase()).collect();
+    let pages = metadata::load(worktree)?;
+    let mut text = String::new();
+    for page in pages
+        .iter()
+        .filter(|page| {
+            let haystack = format!("{} {}", page.title, page.slug).to_lowercase();
+            words.iter().all(|word| haystack.contains(word))
+        })
+        .take(MAX_COMPLETIONS)
+    {
+        text.push_str(&format!("{}  /{}  {}\n", page.title, page.slug, page.path));
+    }
+    if text.is_empty() {
+        text.push_str("No matching pages\n");
+    }
+    Ok(output(text, format!("Pages matching {}", args.join(" "))))
+}
+
+/// Pages linking to a page, given by path or slug.
+fn find_references(args: &[String], worktree: &Worktree) -> Result<SlashCommandOutput> {
+    let target = args.first().ok_or("missing path or slug of the page")?;
+    let slug = if target.ends_with(".md") {
+        let source = worktree.read_text_file(target)?;
+        front_matter::FrontMatter::parse(&source)
+            .and_then(|fm| fm.value("slug"))
+            .ok_or(format!("{target} has no slug"))?
+    } else {
+        target.trim_start_matches('/').to_string()
+    };
+    let references = references::find(worktree, &slug)?;
+    let mut text = format!("{} references to {slug}\n", references.len());
+    for reference in references {
+        text.push_str(&format!("{reference}\n"));
+    }
+    Ok(output(text, format!("References to {slug}")))
+}
+
+/// Links to headings that were renamed or removed on the branch, as a
+/// fix-up list.
+fn check_anchors(args: &[String], worktree: &Worktree) -> Result<SlashCommandOutput> {
+    let base = args.first().map(String::as_str).unwrap_or("origin/main");
+    let root = worktree.root_path();
+    let merge_base = git::run(&root, &["merge-base", base, "HEAD"])?;
+    let mut text = String::new();
+    for path in git::changed_files(&root, base)?
+        .iter()
+        .filter(|path| path.starts_with("files/") && path.ends_with(".md"))
+    {
+        // New pages have nothing to break.
+        let Ok(broken) = anchors::broken_by_edit(worktree, path, &merge_base) else {
+            continue;
+        };
+        for anchor in broken {
+            text.push_str(&format!(
+                "- [ ] {}: {} (heading changed in {path})\n",
+                anchor.location, anchor.link
+            ));
+        }
+    }
+    if text.is_empty() {
+        text.push_str(&format!(
+            "No links broken by heading changes since {base}\n"
+        ));
+    }
+    Ok(output(text, "Anchor fix-ups".to_string()))
+}
+
+/// Pages touched on the current branch, by slug.
+fn changed_pages(args: &[String], worktree: &Worktree) -> Result<SlashCommandOutput> {
+    let base = args.first().map(String::as_str).unwrap_or("origin/main");
+    let files = git::changed_files(&worktree.root_path(), base)?;
+    let mut text = String::new();
+    for path in files
+        .iter()
+        .filter(|path| path.starts_with("files/") && path.ends_with(".md"))
+    {
+        let slug = worktree
+            .read_text_file(path)
+            .ok()
+            .and_then(|source| front_matter::FrontMatter::parse(&source)?.value("slug"));
+        match slug {
+            Some(slug) => text.push_str(&format!("{slug}  {path}\n")),
+            None => text.push_str(&format!("{path}\n")),
+        }
+    }
+    if text.is_empty() {
+        text.push_str(&format!("No pages changed since {base}\n"));
+    }
+    Ok(output(text, format!("Pages changed since {base}")))
+}
+
+/// The checks of content CI on the files changed on the branch, one section
+/// per check.
+fn validate_changes(mdn: &MDN, args: &[String], worktree: &Worktree) -> Result<SlashCommandOutput> {
+    let base = args.first().map(String::as_str).unwrap_or("origin/main");
+    let binary = mdn.installed_rari(worktree)?;
+    let (changed, checks) = ci::validate(worktree, &binary, base)?;
+    if changed.is_empty() {
+        return Ok(output(
+            format!("No content changed since {base}\n"),
+            "Validation".to_string(),
+        ));
+    }
+    let failed = checks.iter().filter(|check| !check.passed).count();
+    let mut text = format!(
+        "{} changed files, {failed} of {} checks failed\n",
+        changed.len(),
+        checks.len()
+    );
+    let mut sections = vec![SlashCommandOutputSection {
+        range: (0..text.len()).into(),
+        label: "Validation".to_string(),
+    }];
+    for check in checks {
+        let start = text.len();
+        let mark = if check.passed { "passed" } else { "FAILED" };
+        text.push_str(&format!("\n{}: {mark}\n", check.name));
+        if !check.output.is_empty() {
+            text.push_str(&format!("{}\n", check.output));
+        }
+        sections.push(SlashCommandOutputSection {
+            range: (start..text.len()).into(),
+            label: format!("{} ({mark})", check.name),
+        });
+    }
+    Ok(SlashCommandOutput { sections, text })
+}
+
+/// Diff of the rendered pages between `HEAD` and the working tree, for the
+/// given pages or the ones with uncommitted changes. `clean` removes the git
+/// worktree `HEAD` is rendered from.
+fn render_diff(mdn: &MDN, args: &[String], worktree: &Worktree) -> Result<SlashCommandOutput> {
+    if args.first().is_some_and(|arg| arg == "clean") {
+        let text = match render_diff::clean(worktree)? {
+            true => "Removed the worktree HEAD is rendered from\n",
+            false => "No worktree to remove\n",
+        };
+        return Ok(output(text.to_string(), "Rendered diff".to_string()));
+    }
+    let files: Vec<String> = if args.is_empty() {
+        git::run(
+            &worktree.root_path(),
+            &[
+                "diff",
+                "--name-only",
+                "--diff-filter=M",
+                "HEAD",
+                "--",
+                "files/*.md",
+            ],
+        )?
+        .lines()
+        .map(String::from)
+        .collect()
+    } else {
+        args.to_vec()
+    };
+    if files.is_empty() {
+        return Err("no changed pages, pass the pages to render".to_string());
+    }
+    let binary = mdn.installed_rari(worktree)?;
+    let diff = render_diff::diff(worktree, &binary, &files)?;
+    let text = if diff.is_empty() {
+        format!("Rendering of {} pages is unchanged\n", files.len())
+    } else {
+        diff
+    };
+    Ok(output(text, "Rendered diff".to_string()))
+}
+
+fn find_macro_source(args: &[String], worktree: &Worktree) -> Result<SlashCommandOutput> {
+    let settings = MdnSettings::for_worktree(worktree);
+    if !settings.developer_mode {
+        return Err("/mdn-macro-source needs `developer_mode` enabled".to_string());
+    }
+    let checkout = settings
+        .rari_checkout
+        .ok_or("set `rari_checkout` to the path of a rari checkout")?;
+    let name = args.first().ok_or("missing macro name")?;
+    let name = name.trim_matches(['{', '}', ' ']);
+    let name = name.split('(').next().unwrap_or(name);
+    let locations = macro_source::locate(&checkout, name)?;
+    if locations.is_empty() {
+        return Err(format!("no implementation of {name} found in {checkout}"));
+    }
+    Ok(output(
+        format!("{}\n", locations.join("\n")),
+        format!("Source of {name}"),
+    ))
+}
+
+fn validate_sidebar(args: &[String], worktree: &Worktree) -> Result<SlashCommandOutput> {
+    let path = args.first().ok_or("missing path of the sidebar")?;
+    let source = worktree.read_text_file(path)?;
+    let broken = sidebar::broken_links(worktree, &source);
+    let mut text = String::new();
+    if broken.is_empty() {
+        text.push_str(&format!("{path}: all linked pages exist\n"));
+    }
+    for link in broken {
+        text.push_str(&format!(
+            "{path}:{}: no page for /{}\n",
+            link.line + 1,
+            link.slug
+        ));
+    }
+    Ok(output(text, format!("Sidebar {path}")))
+}
+
+fn output(text: String, label: String) -> SlashCommandOutput {
+    SlashCommandOutput {
+        sections: vec![SlashCommandOutputSection {
+            range: (0..text.len()).into(),
+            label,
+        }],
+        text,
+    }
+}
@@ -0,0 +1,39 @@
+//! Local rari development: build the language server from a source checkout.
+
+use zed_extension_api::{self as zed, process::Command, LanguageServerId, Result};
+
+/// Runs `cargo build --release` in `checkout` and returns the built binary.
+/// Cargo only rebuilds what changed, so this is cheap when nothing did.
+pub fn build(
+    language_server_id: &LanguageServerId,
+    checkout: &str,
+    environment: &[(String, String)],
+) -> Result<String> {
+    zed::set_language_server_installation_status(
+        language_server_id,
+        &zed::LanguageServerInstallationStatus::CheckingForUpdate,
+    );
+    println!("mdn: building rari in {checkout}");
+    let output = Command::new("cargo")
+        .args(["build", "--release", "--bin", "rari", "--manifest-path"])
+        .arg(format!("{checkout}/Cargo.toml"))
+        .envs(environment.iter().cloned())
+        .output()?;
+    if output.status != Some(0) {
+        return Err(format!(
+            "cargo build in {checkout} failed:\n{}",
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+
+    let target_dir = environment
+        .iter()
+        .find(|(key, _)| key == "CARGO_TARGET_DIR")
+        .map(|(_, value)| value.clone())
+        .unwrap_or_else(|| format!("{checkout}/target"));
+    let (platform, _) = zed::current_platform();
+    Ok(match platform {
+        zed::Os::Mac | zed::Os::Linux => format!("{target_dir}/release/rari"),
+        zed::Os::Windows => format!("{target_dir}/release/rari.exe"),
+    })
+}
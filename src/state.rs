@@ -0,0 +1,72 @@
+//! Extension state that outlives a Zed session, in `state.json` in the work
+//! directory.
+
+use std::{
+    fs,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+use zed_extension_api::serde_json;
+
+use crate::{local_archive::ArchiveHash, release_notes::ReleaseNotes};
+
+const STATE_FILE: &str = "state.json";
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct State {
+    /// Unix time of the last lookup of the latest rari release.
+    pub last_update_check: Option<u64>,
+    /// Latest rari release seen by that lookup.
+    pub latest_version: Option<String>,
+    /// Notes of the last managed upgrade, see [`crate::release_notes`].
+    pub release_notes: Option<ReleaseNotes>,
+    /// The managed rari last resolved, so reloading the extension during
+    /// development doesn't resolve or install it again.
+    pub binary_path: Option<String>,
+    /// Hash of the settings `binary_path` was resolved with.
+    pub settings_hash: Option<u64>,
+    /// The `local_archive` last hashed.
+    pub local_archive: Option<ArchiveHash>,
+}
+
+impl State {
+    pub fn load() -> Self {
+        fs::read_to_string(STATE_FILE)
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        if let Ok(json) = serde_json::to_string_pretty(self) {
+            fs::write(STATE_FILE, json).ok();
+        }
+    }
+
+    pub fn update_check_due(&self, interval_hours: u64) -> bool {
+        self.last_update_check
+            .is_none_or(|last| now().saturating_sub(last) >= interval_hours * 60 * 60)
+    }
+
+    /// Counts a failed lookup as a check, so an offline machine doesn't try
+    /// again on every start.
+    pub fn defer_update_check(&mut self) {
+        self.last_update_check = Some(now());
+        self.save();
+    }
+
+    pub fn record_update_check(&mut self, latest_version: &str) {
+        self.last_update_check = Some(now());
+        self.latest_version = Some(latest_version.to_string());
+        self.save();
+    }
+}
+
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
@@ -0,0 +1,77 @@
+//! Statistics about the content tree, built on the [`ContentIndex`].
+
+use std::{cmp::Reverse, collections::BTreeMap};
+
+use zed_extension_api::Worktree;
+
+use crate::{front_matter, git, index::ContentIndex, redirects};
+
+/// How many of the largest pages to list.
+const LARGEST_PAGES: usize = 10;
+
+/// A plain text report of pages per locale and page type, redirects per
+/// locale and the largest pages.
+pub fn report(worktree: &Worktree, index: &ContentIndex) -> String {
+    let root = worktree.root_path();
+    let mut locales: BTreeMap<&str, usize> = BTreeMap::new();
+    for file in &index.files {
+        if let Some(locale) = file
+            .strip_prefix("files/")
+            .and_then(|f| f.split('/').next())
+        {
+            *locales.entry(locale).or_default() += 1;
+        }
+    }
+
+    let mut text = format!("{} pages\n\nPages per locale:\n", index.files.len());
+    for (locale, pages) in &locales {
+        let redirects = worktree
+            .read_text_file(&redirects::redirects_file(locale))
+            .map(|redirects| {
+                redirects
+                    .lines()
+                    .filter(|line| !line.trim().is_empty() && !line.starts_with('#'))
+                    .count()
+            })
+            .unwrap_or_default();
+        text.push_str(&format!("  {locale}: {pages} ({redirects} redirects)\n"));
+    }
+
+    // Reading every page's front matter through the worktree is slow on
+    // mdn/content, git finds the keys in one go.
+    let mut page_types: BTreeMap<String, usize> = BTreeMap::new();
+    if let Ok(lines) = git::run(&root, &["grep", "-h", "^page-type:", "--", "files/*.md"]) {
+        for line in lines.lines() {
+            let page_type = line.trim_start_matches("page-type:").trim();
+            *page_types
+                .entry(front_matter::unquote(page_type))
+                .or_default() += 1;
+        }
+    }
+    if !page_types.is_empty() {
+        let mut page_types: Vec<_> = page_types.into_iter().collect();
+        page_types.sort_by_key(|&(_, pages)| Reverse(pages));
+        text.push_str("\nPages per page type:\n");
+        for (page_type, pages) in page_types {
+            text.push_str(&format!("  {page_type}: {pages}\n"));
+        }
+    }
+
+    // `ls-tree -l` lines look like `<mode> blob <hash> <size>\t<path>`.
+    if let Ok(tree) = git::run(&root, &["ls-tree", "-r", "-l", "HEAD", "--", "files/"]) {
+        let mut sizes: Vec<(u64, &str)> = tree
+            .lines()
+            .filter_map(|line| {
+                let (meta, path) = line.split_once('\t')?;
+                let size = meta.split_whitespace().nth(3)?.parse().ok()?;
+                path.ends_with(".md").then_some((size, path))
+            })
+            .collect();
+        sizes.sort_by_key(|&(size, _)| Reverse(size));
+        text.push_str("\nLargest pages:\n");
+        for (size, path) in sizes.into_iter().take(LARGEST_PAGES) {
+            text.push_str(&format!("  {path}: {} KiB\n", size / 1024));
+        }
+    }
+    text
+}
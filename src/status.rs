@@ -0,0 +1,115 @@
+//! Which rari is active and where it came from.
+
+use std::{collections::HashMap, fmt};
+
+use zed_extension_api::process::Command;
+
+use crate::{export, state::State};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinarySource {
+    Developer,
+    Settings,
+    Source,
+    Environment,
+    NodeModules,
+    VersionManager,
+    Path,
+    Homebrew,
+    Managed,
+}
+
+impl fmt::Display for BinarySource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            BinarySource::Developer => "developer_rari",
+            BinarySource::Settings => "settings",
+            BinarySource::Source => "source",
+            BinarySource::Environment => "RARI_BINARY_PATH",
+            BinarySource::NodeModules => "from node_modules",
+            BinarySource::VersionManager => "mise/asdf",
+            BinarySource::Path => "PATH",
+            BinarySource::Homebrew => "Homebrew",
+            BinarySource::Managed => "managed",
+        })
+    }
+}
+
+/// The oldest rari the extension drives. Binaries found on the system that
+/// are older are skipped in favor of a managed install.
+pub const MIN_VERSION: &str = "0.1.23";
+
+/// Whether `version` is at least [`MIN_VERSION`], comparing the numeric
+/// components of `1.2.3`. Unparsable versions pass.
+pub fn is_supported(version: &str) -> bool {
+    let parse = |version: &str| -> Option<Vec<u64>> {
+        version
+            .trim_start_matches('v')
+            .split(['-', '+'])
+            .next()?
+            .split('.')
+            .map(|part| part.parse().ok())
+            .collect()
+    };
+    match (parse(version), parse(MIN_VERSION)) {
+        (Some(version), Some(min)) => version >= min,
+        _ => true,
+    }
+}
+
+/// Whether the rari found at `path` can be used, logging why not otherwise.
+/// A binary whose version can't be read is used anyway, so its own error
+/// tells what is wrong. Results are kept in `checked` by path, so `rari
+/// --version` runs once per binary rather than on every start.
+pub fn meets_minimum(
+    path: &str,
+    source: BinarySource,
+    checked: &mut HashMap<String, bool>,
+) -> bool {
+    if let Some(usable) = checked.get(path) {
+        return *usable;
+    }
+    let usable = match version(path, source) {
+        Some(version) if is_supported(&version) => true,
+        Some(version) => {
+            println!("mdn: skipping rari {version} at {path} ({source}), {MIN_VERSION} or newer is needed");
+            false
+        }
+        None => {
+            println!(
+                "mdn: can't read the version of the rari at {path} ({source}), using it anyway"
+            );
+            true
+        }
+    };
+    checked.insert(path.to_string(), usable);
+    usable
+}
+
+/// The version of the rari at `path`, from its install directory for managed
+/// installs or `rari --version` otherwise.
+pub fn version(path: &str, source: BinarySource) -> Option<String> {
+    if source == BinarySource::Managed {
+        return export::managed_version(path).map(String::from);
+    }
+    let output = Command::new(path).arg("--version").output().ok()?;
+    String::from_utf8_lossy(&output.stdout)
+        .split_whitespace()
+        .last()
+        .map(String::from)
+}
+
+/// A one line summary like `rari 0.1.23 (managed)`, noting when a newer
+/// release was seen for managed installs.
+pub fn describe(path: &str, source: BinarySource) -> String {
+    let Some(version) = version(path, source) else {
+        return format!("rari ({source})");
+    };
+    let latest = State::load().latest_version;
+    match latest {
+        Some(latest) if source == BinarySource::Managed && latest != version => {
+            format!("rari {version} ({source}, update available: {latest})")
+        }
+        _ => format!("rari {version} ({source})"),
+    }
+}
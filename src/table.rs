@@ -0,0 +1,130 @@
+//! Prettier compatible reflow of a single Markdown table.
+
+#[derive(Clone, Copy)]
+enum Align {
+    None,
+    Left,
+    Center,
+    Right,
+}
+
+/// Finds the table around the zero based `line` and returns its line range
+/// together with the reformatted table.
+pub fn format_at(source: &str, line: usize) -> Option<(std::ops::Range<usize>, String)> {
+    let lines: Vec<&str> = source.lines().collect();
+    let is_row = |i: usize| {
+        lines
+            .get(i)
+            .is_some_and(|l| l.trim_start().starts_with('|'))
+    };
+    if !is_row(line) {
+        return None;
+    }
+    let start = (0..=line).rev().take_while(|&i| is_row(i)).last()?;
+    let end = (line..lines.len()).take_while(|&i| is_row(i)).last()? + 1;
+    format(&lines[start..end]).map(|table| (start..end, table))
+}
+
+fn format(rows: &[&str]) -> Option<String> {
+    let mut rows: Vec<Vec<String>> = rows.iter().map(|row| split_row(row)).collect();
+    if rows.len() < 2 {
+        return None;
+    }
+    let aligns: Vec<Align> = rows
+        .remove(1)
+        .iter()
+        .map(|cell| parse_align(cell))
+        .collect::<Option<_>>()?;
+    let columns = rows.iter().map(Vec::len).chain([aligns.len()]).max()?;
+    for row in &mut rows {
+        row.resize(columns, String::new());
+    }
+    let widths: Vec<usize> = (0..columns)
+        .map(|column| {
+            rows.iter()
+                .map(|row| row[column].chars().count())
+                .max()
+                .unwrap_or(0)
+                .max(3)
+        })
+        .collect();
+    let align = |column: usize| aligns.get(column).copied().unwrap_or(Align::None);
+
+    let render = |cells: Vec<String>| format!("| {} |", cells.join(" | "));
+    let mut out = vec![];
+    let mut rows = rows.into_iter();
+    let pad_row = |row: Vec<String>| {
+        row.into_iter()
+            .enumerate()
+            .map(|(column, cell)| pad(&cell, widths[column], align(column)))
+            .collect()
+    };
+    out.push(render(pad_row(rows.next()?)));
+    out.push(render(
+        (0..columns)
+            .map(|column| delimiter(widths[column], align(column)))
+            .collect(),
+    ));
+    out.extend(rows.map(|row| render(pad_row(row))));
+    Some(out.join("\n"))
+}
+
+fn split_row(row: &str) -> Vec<String> {
+    let row = row.trim();
+    let row = row.strip_prefix('|').unwrap_or(row);
+    let mut cells = vec![];
+    let mut cell = String::new();
+    let mut in_code = false;
+    let mut escaped = false;
+    for c in row.chars() {
+        match c {
+            '|' if !in_code && !escaped => cells.push(std::mem::take(&mut cell)),
+            _ => {
+                if c == '`' && !escaped {
+                    in_code = !in_code;
+                }
+                escaped = c == '\\' && !escaped;
+                cell.push(c);
+            }
+        }
+    }
+    if !cell.trim().is_empty() {
+        cells.push(cell);
+    }
+    cells
+        .into_iter()
+        .map(|cell| cell.trim().to_string())
+        .collect()
+}
+
+fn parse_align(cell: &str) -> Option<Align> {
+    let dashes = cell.trim_start_matches(':').trim_end_matches(':');
+    if dashes.is_empty() || !dashes.chars().all(|c| c == '-') {
+        return None;
+    }
+    Some(match (cell.starts_with(':'), cell.ends_with(':')) {
+        (true, true) => Align::Center,
+        (true, false) => Align::Left,
+        (false, true) => Align::Right,
+        (false, false) => Align::None,
+    })
+}
+
+fn pad(cell: &str, width: usize, align: Align) -> String {
+    let fill = width - cell.chars().count();
+    let (left, right) = match align {
+        Align::None | Align::Left => (0, fill),
+        Align::Right => (fill, 0),
+        Align::Center => (fill / 2, fill - fill / 2),
+    };
+    format!("{}{cell}{}", " ".repeat(left), " ".repeat(right))
+}
+
+fn delimiter(width: usize, align: Align) -> String {
+    match align {
+        Align::None => "-".repeat(width),
+        Align::Left => format!(":{}", "-".repeat(width - 1)),
+        Align::Right => format!("{}:", "-".repeat(width - 1)),
+        Align::Center => format!(":{}:", "-".repeat(width - 2)),
+    }
+}
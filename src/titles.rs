@@ -0,0 +1,104 @@
+//! The front-matter title against the page body and the title formats of
+//! `data/page_types.json`.
+//!
+//! rari renders the title as the page's only level 1 heading, so a `#`
+//! heading in the body shows up as a second one.
+
+use crate::{
+    front_matter::FrontMatter,
+    markdown::{self, Heading},
+    page_types,
+};
+
+/// The title a page's type asks for, with `*` for free text, if it doesn't
+/// have it.
+pub fn misformatted(front_matter: &FrontMatter) -> Option<String> {
+    let template = page_types::template(&front_matter.page_type()?)?;
+    let expected = template.title_for(&front_matter.value("slug")?)?;
+    let title = front_matter.title()?;
+    (!matches(&expected, &title)).then_some(expected)
+}
+
+/// Whether `title` has the format `expected`, where one `*` is any text.
+fn matches(expected: &str, title: &str) -> bool {
+    match expected.split_once('*') {
+        Some((prefix, suffix)) => {
+            title.len() > prefix.len() + suffix.len()
+                && title.starts_with(prefix)
+                && title.ends_with(suffix)
+        }
+        None => title == expected,
+    }
+}
+
+/// The level 1 headings of the body.
+pub fn body_headings(source: &str) -> Vec<Heading> {
+    markdown::headings(source)
+        .filter(|heading| heading.level == 1)
+        .collect()
+}
+
+/// A replacement for the first lines of `source`: the front-matter with the
+/// title its page type asks for, and the body up to its last level 1
+/// heading with those removed. `None` when there is nothing to fix. Formats
+/// with free text leave the title alone.
+pub fn fix(source: &str) -> Option<(usize, String)> {
+    let mut front_matter = FrontMatter::parse(source)?;
+    let title = misformatted(&front_matter).filter(|title| !title.contains('*'));
+    let headings = body_headings(source);
+    if title.is_none() && headings.is_empty() {
+        return None;
+    }
+    if let Some(title) = &title {
+        front_matter.set("title", &quote(title));
+    }
+    let lines: Vec<&str> = source.lines().collect();
+    let front_matter_end = 1 + front_matter
+        .entries
+        .iter()
+        .map(|entry| 1 + entry.nested.len())
+        .sum::<usize>();
+    let end = match headings.last() {
+        // With the blank line after it.
+        Some(heading)
+            if lines
+                .get(heading.line + 1)
+                .is_some_and(|l| l.trim().is_empty()) =>
+        {
+            heading.line + 2
+        }
+        Some(heading) => heading.line + 1,
+        None => front_matter_end + 1,
+    };
+    let mut text = front_matter.to_block();
+    text.push('\n');
+    let mut skip_blank = false;
+    for (line, content) in lines
+        .iter()
+        .enumerate()
+        .take(end)
+        .skip(front_matter_end + 1)
+    {
+        if headings.iter().any(|heading| heading.line == line) {
+            skip_blank = true;
+            continue;
+        }
+        if skip_blank && content.trim().is_empty() {
+            skip_blank = false;
+            continue;
+        }
+        skip_blank = false;
+        text.push_str(content);
+        text.push('\n');
+    }
+    Some((end, text))
+}
+
+/// Titles with YAML special characters need quotes.
+pub fn quote(title: &str) -> String {
+    if title.contains([':', '#', '"']) || title.starts_with(['<', '[', '{', '*', '&', '!']) {
+        format!("\"{}\"", title.replace('"', "\\\""))
+    } else {
+        title.to_string()
+    }
+}
@@ -0,0 +1,111 @@
+//! Bundles of the installed rari for distributing one vetted version across
+//! a team: `/mdn-toolchain export <dir>` writes the binary as an archive with
+//! a manifest of its version and checksums, `/mdn-toolchain import <dir>`
+//! checks a bundle and prints the settings installing it through
+//! `local_archive`, without anyone going to GitHub.
+
+use std::{env, fs};
+
+use serde::{Deserialize, Serialize};
+use zed_extension_api::{self as zed, process::Command, serde_json, Result};
+
+use crate::{
+    assets, local_archive,
+    status::{self, BinarySource},
+};
+
+const MANIFEST_FILE: &str = "rari-toolchain.json";
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Manifest {
+    pub version: String,
+    /// [`assets::platform_key`] of the machine the bundle was exported on.
+    pub platform: String,
+    /// File name of the archive next to the manifest.
+    pub archive: String,
+    pub archive_sha256: String,
+    /// Checked after unpacking, as the platform's `assets` `sha256`.
+    pub binary_sha256: String,
+}
+
+fn platform_key() -> String {
+    let (platform, arch) = zed::current_platform();
+    assets::platform_key(arch, platform)
+}
+
+fn run(program: &str, args: &[&str]) -> Result<String> {
+    let output = Command::new(program).args(args.iter().copied()).output()?;
+    if output.status != Some(0) {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Writes the rari at `binary_path` and its manifest to the absolute `dir`.
+pub fn export(binary_path: &str, dir: &str) -> Result<Manifest> {
+    let work_dir = env::current_dir().map_err(|e| format!("no work directory: {e}"))?;
+    // Managed installs are relative to the work directory.
+    let binary_path = work_dir.join(binary_path).to_string_lossy().to_string();
+    let (binary_dir, binary) = binary_path
+        .rsplit_once('/')
+        .ok_or(format!("not a path to rari: {binary_path}"))?;
+    let version = status::version(&binary_path, BinarySource::Path)
+        .ok_or(format!("{binary_path} doesn't run"))?;
+    let platform = platform_key();
+    let archive = format!("rari-{version}-{platform}.tar.gz");
+    let archive_path = format!("{dir}/{archive}");
+    run("mkdir", &["-p", dir])
+        .and_then(|_| run("tar", &["-czf", &archive_path, "-C", binary_dir, binary]))
+        .map_err(|e| format!("failed to write {archive_path}: {e}"))?;
+    let manifest = Manifest {
+        version,
+        platform,
+        archive,
+        archive_sha256: local_archive::sha256(&archive_path)?,
+        binary_sha256: local_archive::sha256(&binary_path)?,
+    };
+    let json = serde_json::to_string_pretty(&manifest).map_err(|e| e.to_string())?;
+    // Written to the work directory first, the extension can't write to `dir`.
+    let manifest_path = format!("{dir}/{MANIFEST_FILE}");
+    let written = work_dir.join(MANIFEST_FILE).to_string_lossy().to_string();
+    fs::write(MANIFEST_FILE, format!("{json}\n"))
+        .map_err(|e| e.to_string())
+        .and_then(|_| run("mv", &[&written, &manifest_path]))
+        .map_err(|e| format!("failed to write {manifest_path}: {e}"))?;
+    Ok(manifest)
+}
+
+/// The manifest of the bundle in the absolute `dir` and the path of its
+/// archive, after checking both fit this machine.
+pub fn import(dir: &str) -> Result<(Manifest, String)> {
+    let manifest_path = format!("{dir}/{MANIFEST_FILE}");
+    let json =
+        run("cat", &[&manifest_path]).map_err(|e| format!("no toolchain bundle in {dir}: {e}"))?;
+    let manifest: Manifest =
+        serde_json::from_str(&json).map_err(|e| format!("invalid {manifest_path}: {e}"))?;
+    let platform = platform_key();
+    if manifest.platform != platform {
+        return Err(format!(
+            "the bundle in {dir} is for {}, this machine is {platform}",
+            manifest.platform
+        ));
+    }
+    let archive_path = format!("{dir}/{}", manifest.archive);
+    let actual = local_archive::sha256(&archive_path)?;
+    if !actual.eq_ignore_ascii_case(&manifest.archive_sha256) {
+        return Err(format!(
+            "checksum mismatch for {archive_path}: expected {}, got {actual}",
+            manifest.archive_sha256
+        ));
+    }
+    Ok((manifest, archive_path))
+}
+
+/// The `lsp.mdn-lsp.settings` installing an imported bundle.
+pub fn settings(manifest: &Manifest, archive_path: &str) -> String {
+    let settings = serde_json::json!({
+        "local_archive": archive_path,
+        "assets": { &manifest.platform: { "sha256": &manifest.binary_sha256 } },
+    });
+    serde_json::to_string_pretty(&settings).unwrap_or_default()
+}
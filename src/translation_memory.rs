@@ -0,0 +1,306 @@
+//! Recurring en-US sentences and how a locale translated them, so
+//! translators can keep phrases like "Experimental. Expect behavior to
+//! change." consistent across pages.
+//!
+//! Translated pages are paired with their en-US sources when both have the
+//! same structure (the same headings, paragraphs, list items and notes in
+//! the same order), and then block by block and sentence by sentence.
+//! Reading thousands of page pairs through the worktree is too slow, so like
+//! [`crate::metadata`] `git grep` dumps them into the work directory. The
+//! next server start or `/mdn-translation-memory` turns the dump into the
+//! memory, completions only ever read the finished memory.
+
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    env, fs,
+    hash::{DefaultHasher, Hash, Hasher},
+};
+
+use serde::{Deserialize, Serialize};
+use zed_extension_api::{serde_json, Result, Worktree};
+
+use crate::{content, git, markdown, settings::MdnSettings};
+
+const RECORD_SEPARATOR: char = '\u{1e}';
+
+/// Pathspecs per `git grep` when reading the en-US sources.
+const PATHSPECS_PER_GREP: usize = 500;
+
+/// Sentences translated on fewer pages aren't recurring phrases.
+const MIN_PAGES: usize = 3;
+
+const MAX_ENTRIES: usize = 5000;
+
+/// A recurring en-US sentence or block with its translations.
+#[derive(Serialize, Deserialize)]
+pub struct Entry {
+    pub source: String,
+    /// Number of pages the source was translated on.
+    pub pages: usize,
+    /// Translations with the number of pages using them, most used first.
+    pub translations: Vec<(String, usize)>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Memory {
+    locale: String,
+    /// Commit of the translated checkout the memory was built from.
+    head: String,
+    entries: Vec<Entry>,
+}
+
+/// Where the memory of `worktree` is kept in the work directory. The dump is
+/// next to it with a `.dump` suffix.
+pub fn file(worktree: &Worktree) -> Result<String> {
+    let mut hasher = DefaultHasher::new();
+    worktree.root_path().hash(&mut hasher);
+    let dir = env::current_dir().map_err(|e| format!("no work directory: {e}"))?;
+    Ok(format!(
+        "{}/translation-memory-{:x}.json",
+        dir.to_string_lossy(),
+        hasher.finish()
+    ))
+}
+
+/// Writes a new dump when the memory is missing, of another locale or built
+/// from an older commit, see `translation_memory`.
+pub fn refresh(worktree: &Worktree, settings: &MdnSettings) -> Result<()> {
+    let Some(locale) = settings.translation_memory.as_deref() else {
+        return Ok(());
+    };
+    let locale = locale.to_lowercase();
+    let repo = content::repo_root(worktree);
+    let head = git::run(&repo, &["rev-parse", "HEAD"])?;
+    let file = file(worktree)?;
+    let current = fs::read_to_string(&file)
+        .ok()
+        .and_then(|json| serde_json::from_str::<Memory>(&json).ok())
+        .is_some_and(|memory| memory.locale == locale && memory.head == head);
+    let dumped = fs::read_to_string(format!("{file}.dump.head"))
+        .is_ok_and(|dumped| dumped.trim() == head)
+        && fs::metadata(format!("{file}.dump")).is_ok();
+    if current || dumped {
+        return Ok(());
+    }
+    let pairs = dump(&repo, &content::en_us_repo(worktree, settings), &locale);
+    let dump_file = format!("{file}.dump");
+    fs::write(&dump_file, pairs).map_err(|e| format!("failed to write {dump_file}: {e}"))?;
+    fs::write(format!("{dump_file}.head"), head)
+        .map_err(|e| format!("failed to write {dump_file}: {e}"))
+}
+
+/// The pages of `locale` in `repo` followed by their en-US sources from
+/// `en_us_repo`, each pair headed by a record separator and the page's path.
+fn dump(repo: &str, en_us_repo: &str, locale: &str) -> String {
+    let pages = read_files(repo, &[format!("files/{locale}/*.md")]);
+    let source_path = |page: &str| {
+        let rest = page.strip_prefix(&format!("files/{locale}/"))?;
+        Some(format!("files/en-us/{rest}"))
+    };
+    let source_paths: Vec<String> = pages.keys().filter_map(|page| source_path(page)).collect();
+    let mut sources = BTreeMap::new();
+    for chunk in source_paths.chunks(PATHSPECS_PER_GREP) {
+        sources.append(&mut read_files(en_us_repo, chunk));
+    }
+    let mut pairs = String::new();
+    for (page, translated) in &pages {
+        let Some(source) = source_path(page).and_then(|path| sources.get(&path)) else {
+            continue;
+        };
+        pairs.push_str(&format!(
+            "{RECORD_SEPARATOR}{page}\n{translated}{RECORD_SEPARATOR}\n{source}"
+        ));
+    }
+    pairs
+}
+
+/// The text files of `repo` matching `pathspecs` by path. With an empty
+/// pattern `git grep` prints every line, prefixed with the file's path.
+fn read_files(repo: &str, pathspecs: &[String]) -> BTreeMap<String, String> {
+    let mut args = vec!["grep", "-I", "-e", "", "--"];
+    args.extend(pathspecs.iter().map(String::as_str));
+    // git grep exits with 1 when nothing matches.
+    let output = git::run(repo, &args).unwrap_or_default();
+    let mut files: BTreeMap<String, String> = BTreeMap::new();
+    for line in output.lines() {
+        let Some((path, text)) = line.split_once(':') else {
+            continue;
+        };
+        let file = files.entry(path.to_string()).or_default();
+        file.push_str(text);
+        file.push('\n');
+    }
+    files
+}
+
+/// Builds the memory in `file` from a finished dump, if there is one.
+pub fn finish(file: &str) {
+    let dump = format!("{file}.dump");
+    let Ok(pairs) = fs::read_to_string(&dump) else {
+        return;
+    };
+    let head = fs::read_to_string(format!("{dump}.head")).unwrap_or_default();
+    let memory = build(&pairs, head.trim());
+    if let Ok(json) = serde_json::to_string(&memory) {
+        fs::write(file, json).ok();
+        fs::remove_file(&dump).ok();
+    }
+}
+
+/// The entries of the memory in `file`, `None` until it was built, see
+/// [`finish`].
+pub fn read(file: &str) -> Option<Vec<Entry>> {
+    let json = fs::read_to_string(file).ok()?;
+    serde_json::from_str::<Memory>(&json)
+        .ok()
+        .map(|memory| memory.entries)
+}
+
+/// The entries whose source contains `query`, case-insensitively.
+pub fn search<'a>(entries: &'a [Entry], query: &str) -> impl Iterator<Item = &'a Entry> {
+    let query = query.trim().to_lowercase();
+    entries
+        .iter()
+        .filter(move |entry| entry.source.to_lowercase().contains(&query))
+}
+
+fn build(pairs: &str, head: &str) -> Memory {
+    let mut locale = String::new();
+    // Source → translation → pages using it.
+    let mut seen: HashMap<String, HashMap<String, usize>> = HashMap::new();
+    let mut records = pairs.split(RECORD_SEPARATOR).skip(1);
+    while let (Some(page), Some(source)) = (records.next(), records.next()) {
+        let (path, translated) = page.split_once('\n').unwrap_or((page, ""));
+        let source = source.strip_prefix('\n').unwrap_or(source);
+        if locale.is_empty() {
+            locale = content::locale_of(path).unwrap_or_default().to_string();
+        }
+        let mut on_page = HashSet::new();
+        for pair in align(source, translated) {
+            if on_page.insert(pair.clone()) {
+                *seen.entry(pair.0).or_default().entry(pair.1).or_default() += 1;
+            }
+        }
+    }
+    let mut entries: Vec<Entry> = seen
+        .into_iter()
+        .filter_map(|(source, translations)| {
+            let pages = translations.values().sum();
+            let mut translations: Vec<(String, usize)> = translations.into_iter().collect();
+            translations.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+            (pages >= MIN_PAGES).then_some(Entry {
+                source,
+                pages,
+                translations,
+            })
+        })
+        .collect();
+    entries.sort_by(|a, b| b.pages.cmp(&a.pages).then_with(|| a.source.cmp(&b.source)));
+    entries.truncate(MAX_ENTRIES);
+    Memory {
+        locale,
+        head: head.to_string(),
+        entries,
+    }
+}
+
+/// The en-US and translated sentences of two pages of the same structure,
+/// nothing otherwise.
+fn align(source: &str, translated: &str) -> Vec<(String, String)> {
+    let source = blocks(source);
+    let translated = blocks(translated);
+    let same_structure =
+        source.len() == translated.len() && source.iter().zip(&translated).all(|(s, t)| s.0 == t.0);
+    if !same_structure {
+        return vec![];
+    }
+    let mut pairs = vec![];
+    for ((_, source), (_, translated)) in source.into_iter().zip(translated) {
+        let source_sentences = sentences(&source);
+        let translated_sentences = sentences(&translated);
+        if source_sentences.len() == translated_sentences.len() {
+            pairs.extend(source_sentences.into_iter().zip(translated_sentences));
+        } else {
+            pairs.push((source, translated));
+        }
+    }
+    pairs.retain(|(source, translated)| {
+        // Untranslated text and lone macro calls.
+        source != translated && source.chars().any(char::is_alphabetic)
+    });
+    pairs
+}
+
+/// The kind of a block of prose, which must match between a page and its
+/// translation.
+#[derive(PartialEq)]
+enum Block {
+    Heading(usize),
+    ListItem,
+    Quote,
+    Table,
+    Paragraph,
+}
+
+/// The blocks of prose of a page with their text on one line, markers
+/// removed.
+fn blocks(source: &str) -> Vec<(Block, String)> {
+    let mut blocks: Vec<(Block, String)> = vec![];
+    let mut previous_line = None;
+    for prose in markdown::prose_lines(source) {
+        let text = prose.text.trim();
+        let continues = previous_line == Some(prose.line.wrapping_sub(1));
+        previous_line = Some(prose.line);
+        if text.is_empty() {
+            previous_line = None;
+            continue;
+        }
+        let (kind, text) = if let Some(rest) = text.strip_prefix('#') {
+            let level = 1 + rest.len() - rest.trim_start_matches('#').len();
+            (Block::Heading(level), rest.trim_start_matches('#').trim())
+        } else if let Some(rest) = text.strip_prefix("- ").or(text.strip_prefix("* ")) {
+            (Block::ListItem, rest)
+        } else if text.starts_with('|') {
+            (Block::Table, text)
+        } else if let Some(rest) = text.strip_prefix('>') {
+            (Block::Quote, rest.trim())
+        } else {
+            (Block::Paragraph, text)
+        };
+        match blocks.last_mut() {
+            // Wrapped lines of the same paragraph or quote.
+            Some((last, last_text))
+                if continues
+                    && matches!(kind, Block::Paragraph | Block::Quote)
+                    && matches!(last, Block::Paragraph | Block::ListItem | Block::Quote) =>
+            {
+                last_text.push(' ');
+                last_text.push_str(text);
+            }
+            _ => blocks.push((kind, text.to_string())),
+        }
+    }
+    blocks
+}
+
+/// `text` split after sentence ending punctuation.
+fn sentences(text: &str) -> Vec<String> {
+    let mut sentences = vec![];
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        let end = i + c.len_utf8();
+        let ends_sentence = match c {
+            '。' | '！' | '？' => true,
+            '.' | '!' | '?' => chars.peek().is_some_and(|(_, next)| *next == ' '),
+            _ => false,
+        };
+        if ends_sentence {
+            sentences.push(text[start..end].trim().to_string());
+            start = end;
+        }
+    }
+    sentences.push(text[start..].trim().to_string());
+    sentences.retain(|sentence| !sentence.is_empty());
+    sentences
+}
@@ -0,0 +1,30 @@
+//! rari installed through a version manager like mise or asdf.
+//!
+//! Their `rari` on the `PATH` is a shim that picks a version per directory,
+//! so we ask the manager for the real binary in the worktree instead. That
+//! keeps the extension on the same rari as the team's pinned toolchain, and
+//! the status shows the actual path rather than the shim.
+
+use zed_extension_api::{process::Command, Worktree};
+
+use crate::processes;
+
+const MANAGERS: &[&str] = &["mise", "asdf"];
+
+/// The real path of the rari a version manager selects for the worktree.
+pub fn resolve(worktree: &Worktree, environment: &[(String, String)]) -> Option<String> {
+    MANAGERS.iter().find_map(|&name| {
+        worktree.which(name)?;
+        // Both read their tool versions from the working directory. The
+        // manager is run by name, `extension.toml` allows exactly these.
+        let output = Command::new("sh")
+            .args(["-c", processes::IN_DIR_SCRIPT])
+            .arg(worktree.root_path())
+            .args([name, "which", "rari"])
+            .envs(environment.iter().cloned())
+            .output()
+            .ok()?;
+        let path = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        (output.status == Some(0) && path.starts_with('/')).then_some(path)
+    })
+}
@@ -0,0 +1,83 @@
+//! Cross reference macros (`{{domxref}}`, `{{cssxref}}`, …) and the part of
+//! the content tree each of them points into.
+
+use zed_extension_api::Worktree;
+
+use crate::content::{slug_to_file_path, DocUrl};
+
+struct XrefMacro {
+    name: &'static str,
+    slug_prefixes: &'static [&'static str],
+}
+
+const XREF_MACROS: &[XrefMacro] = &[
+    XrefMacro {
+        name: "domxref",
+        slug_prefixes: &["Web/API/"],
+    },
+    XrefMacro {
+        name: "cssxref",
+        slug_prefixes: &["Web/CSS/"],
+    },
+    XrefMacro {
+        name: "jsxref",
+        slug_prefixes: &["Web/JavaScript/Reference/Global_Objects/"],
+    },
+    XrefMacro {
+        name: "HTTPHeader",
+        slug_prefixes: &["Web/HTTP/Reference/Headers/", "Web/HTTP/Headers/"],
+    },
+];
+
+fn call(macro_name: &str, target: &str) -> String {
+    format!("{{{{{macro_name}(\"{target}\")}}}}")
+}
+
+/// The xref call linking to `url`, if its slug lives in a section with a
+/// dedicated xref macro.
+pub fn from_url(url: &DocUrl, page_type: Option<&str>) -> Option<String> {
+    if url.fragment.is_some() {
+        return None;
+    }
+    XREF_MACROS.iter().find_map(|xref| {
+        let rest = xref
+            .slug_prefixes
+            .iter()
+            .find_map(|prefix| url.slug.strip_prefix(prefix))?;
+        let mut target = rest.replace('/', ".");
+        // `Array/map` is written `Array.prototype.map()`.
+        if matches!(
+            page_type,
+            Some("javascript-instance-method" | "javascript-instance-accessor-property")
+        ) {
+            if let Some((object, member)) = target.split_once('.') {
+                target = format!("{object}.prototype.{member}");
+            }
+        }
+        if page_type.is_some_and(|page_type| page_type.ends_with("-method")) {
+            target.push_str("()");
+        }
+        Some(call(xref.name, &target))
+    })
+}
+
+/// Wraps an API name like `HTMLCanvasElement.getContext()` in the xref macro
+/// whose section of the local en-US tree contains a matching page. Instance
+/// members like `Array.prototype.map()` have their pages without `prototype`.
+pub fn for_name(worktree: &Worktree, name: &str) -> Option<String> {
+    let name = name.trim();
+    let path = name
+        .trim_end_matches("()")
+        .replace(".prototype.", ".")
+        .replace('.', "/");
+    XREF_MACROS.iter().find_map(|xref| {
+        xref.slug_prefixes
+            .iter()
+            .any(|prefix| {
+                worktree
+                    .read_text_file(&slug_to_file_path("en-US", &format!("{prefix}{path}")))
+                    .is_ok()
+            })
+            .then(|| call(xref.name, name))
+    })
+}